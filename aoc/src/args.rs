@@ -0,0 +1,194 @@
+use std::fmt;
+
+pub const USAGE : &str = "Usage:
+    aoc run <day> [--part N] [--input PATH] [--time | --bench N] [--json]
+    aoc run all [--part N] [--time | --bench N] [--json] [--root DIR]
+    aoc record <day|all> [--part N] [--input PATH] [--answers PATH] [--root DIR]
+    aoc verify <day|all> [--part N] [--input PATH] [--answers PATH] [--root DIR]
+
+The day_N/input.txt inputs and answers.toml are looked for under --root,
+which defaults to the current directory.";
+
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    Run,
+    Record,
+    Verify,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Days {
+    One(u32),
+    All,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Command {
+    pub action : Action,
+    pub days : Days,
+    pub part : Option<u32>,
+    pub input : Option<String>,
+    pub answers : Option<String>,
+    // Where day_N/input.txt and answers.toml live, when not the current directory.
+    pub root : Option<String>,
+    // Number of timed runs, when timing was asked for with --time or --bench.
+    pub bench : Option<u32>,
+    pub json : bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ArgsError(String);
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn error<T>(message : String) -> Result<T, ArgsError> {
+    Err(ArgsError(message))
+}
+
+pub fn parse_args(args : &[String]) -> Result<Command, ArgsError> {
+    let mut args = args.iter();
+
+    let action = match args.next().map(|s| s.as_str()) {
+        Some("run") => Action::Run,
+        Some("record") => Action::Record,
+        Some("verify") => Action::Verify,
+        Some(other) => return error(format!("Unknown command '{}'", other)),
+        None => return error("No command given".to_string()),
+    };
+
+    let days = match args.next().map(|s| s.as_str()) {
+        Some("all") => Days::All,
+        Some(day) => Days::One(parse_number(day, "day")?),
+        None => return error("No day given".to_string()),
+    };
+
+    let mut part = None;
+    let mut input = None;
+    let mut answers = None;
+    let mut root = None;
+    let mut bench = None;
+    let mut json = false;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| ArgsError(format!("{} needs a value", arg)));
+        match arg.as_str() {
+            "--part" => {
+                let n = parse_number(value()?, "part")?;
+                if n != 1 && n != 2 {
+                    return error(format!("Part must be 1 or 2, not {}", n));
+                }
+                part = Some(n);
+            },
+            "--input" => input = Some(value()?.to_string()),
+            "--answers" if action != Action::Run => answers = Some(value()?.to_string()),
+            "--root" => root = Some(value()?.to_string()),
+            "--time" if action == Action::Run => bench = Some(1),
+            "--bench" if action == Action::Run => {
+                let n = parse_number(value()?, "run")?;
+                if n == 0 {
+                    return error("--bench needs at least 1 run".to_string());
+                }
+                bench = Some(n);
+            },
+            "--json" if action == Action::Run => json = true,
+            other => return error(format!("Unexpected argument '{}'", other)),
+        }
+    }
+
+    if days == Days::All && input.is_some() {
+        return error("--input can't be used with 'all'".to_string());
+    }
+
+    if root.is_some() && action == Action::Run && days != Days::All {
+        return error("--root is only used with 'all', record or verify".to_string());
+    }
+
+    if json && bench.is_none() {
+        return error("--json needs --time or --bench".to_string());
+    }
+
+    Ok(Command { action, days, part, input, answers, root, bench, json })
+}
+
+fn parse_number(s : &str, what : &str) -> Result<u32, ArgsError> {
+    s.parse::<u32>().or_else(|_| error(format!("Expected a {} number, got '{}'", what, s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s : &str) -> Vec<String> {
+        s.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    fn run(days : Days, part : Option<u32>, input : Option<&str>) -> Command {
+        Command { action: Action::Run, days, part, input: input.map(|i| i.to_string()), answers: None, root: None, bench: None, json: false }
+    }
+
+    #[test]
+    fn run_a_single_day() {
+        assert_eq!(parse_args(&args("run 3")), Ok(run(Days::One(3), None, None)));
+    }
+
+    #[test]
+    fn run_one_part_from_a_file() {
+        assert_eq!(
+            parse_args(&args("run 5 --part 2 --input day_5/input.txt")),
+            Ok(run(Days::One(5), Some(2), Some("day_5/input.txt"))));
+    }
+
+    #[test]
+    fn run_all() {
+        assert_eq!(parse_args(&args("run all --part 1")), Ok(run(Days::All, Some(1), None)));
+    }
+
+    #[test]
+    fn record_and_verify_take_an_answers_file() {
+        assert_eq!(
+            parse_args(&args("verify all --answers a.toml")),
+            Ok(Command { action: Action::Verify, days: Days::All, part: None, input: None, answers: Some("a.toml".to_string()), root: None, bench: None, json: false }));
+        assert_eq!(
+            parse_args(&args("record 2 --input in.txt")),
+            Ok(Command { action: Action::Record, days: Days::One(2), part: None, input: Some("in.txt".to_string()), answers: None, root: None, bench: None, json: false }));
+    }
+
+    #[test]
+    fn the_root_can_be_moved() {
+        assert_eq!(parse_args(&args("verify all --root /srv/aoc")).unwrap().root, Some("/srv/aoc".to_string()));
+        assert_eq!(parse_args(&args("run all --root ..")).unwrap().root, Some("..".to_string()));
+        assert_eq!(parse_args(&args("record 3")).unwrap().root, None);
+    }
+
+    #[test]
+    fn timing_options() {
+        let command = parse_args(&args("run 3 --time")).unwrap();
+        assert_eq!(command.bench, Some(1));
+        assert!(!command.json);
+
+        let command = parse_args(&args("run all --bench 10 --json")).unwrap();
+        assert_eq!(command.bench, Some(10));
+        assert!(command.json);
+    }
+
+    #[test]
+    fn bad_arguments_are_errors() {
+        assert!(parse_args(&args("")).is_err());
+        assert!(parse_args(&args("walk 1")).is_err());
+        assert!(parse_args(&args("run")).is_err());
+        assert!(parse_args(&args("run one")).is_err());
+        assert!(parse_args(&args("run 1 --part 3")).is_err());
+        assert!(parse_args(&args("run 1 --part")).is_err());
+        assert!(parse_args(&args("run 1 --verbose")).is_err());
+        assert!(parse_args(&args("run all --input x.txt")).is_err());
+        assert!(parse_args(&args("run 1 --answers a.toml")).is_err());
+        assert!(parse_args(&args("run 1 --bench 0")).is_err());
+        assert!(parse_args(&args("run 1 --json")).is_err());
+        assert!(parse_args(&args("verify all --time")).is_err());
+        assert!(parse_args(&args("run 1 --root ..")).is_err());
+        assert!(parse_args(&args("verify all --root")).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::profile::{profile, Profiler};
+use aoc_common::{solve, Answers, Puzzle, SolveError};
+use day_1::Day1;
+use day_2::Day2;
+use day_3::Day3;
+use day_4::Day4;
+use day_5::Day5;
+use day_6::Day6;
+use std::path::Path;
+
+pub type Solver = fn(&str, Option<u32>) -> Result<Answers, SolveError>;
+
+const SOLVERS : &[(u32, Solver, Profiler)] = &[
+    (Day1::DAY, solve::<Day1>, profile::<Day1>),
+    (Day2::DAY, solve::<Day2>, profile::<Day2>),
+    (Day3::DAY, solve::<Day3>, profile::<Day3>),
+    (Day4::DAY, solve::<Day4>, profile::<Day4>),
+    (Day5::DAY, solve::<Day5>, profile::<Day5>),
+    (Day6::DAY, solve::<Day6>, profile::<Day6>),
+];
+
+pub fn solver(day : u32) -> Option<Solver> {
+    SOLVERS.iter().find(|(d, _, _)| *d == day).map(|(_, solve, _)| *solve)
+}
+
+pub fn profiler(day : u32) -> Option<Profiler> {
+    SOLVERS.iter().find(|(d, _, _)| *d == day).map(|(_, _, profile)| *profile)
+}
+
+pub fn solved_days() -> Vec<u32> {
+    SOLVERS.iter().map(|(day, _, _)| *day).collect()
+}
+
+// Where `run all` looks for a day's input: day_N/input.txt under `root`.
+pub fn default_input(root : &str, day : u32) -> String {
+    Path::new(root).join(format!("day_{}", day)).join("input.txt").display().to_string()
+}
+
+// The answers file under `root`, used unless --answers is given.
+pub fn default_answers(root : &str) -> String {
+    Path::new(root).join("answers.toml").display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_asked_for_parts_are_solved() {
+        let solve = solver(1).unwrap();
+
+        let answers = solve("+1\n-2\n+3\n+1\n", Some(1)).unwrap();
+        assert_eq!(answers.part1, Some("3".to_string()));
+        assert_eq!(answers.part2, None);
+
+        let answers = solve("+1\n-2\n+3\n+1\n", None).unwrap();
+        assert_eq!(answers.part1, Some("3".to_string()));
+        assert_eq!(answers.part2, Some("2".to_string()));
+    }
+
+    #[test]
+    fn parse_errors_are_returned() {
+        let solve = solver(1).unwrap();
+        match solve("+1\nx\ny\n", None) {
+            Err(SolveError::Parse(errors)) => assert_eq!(errors.0.len(), 2),
+            other => panic!("expected parse errors, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn failed_parts_are_errors_rather_than_answers() {
+        let solve = solver(1).unwrap();
+        let error = solve("+9223372036854775807\n+1\n", Some(1)).unwrap_err();
+        assert_eq!(error, SolveError::Part { part: 1, reason: "the frequency overflowed at drift 2".to_string() });
+    }
+
+    #[test]
+    fn default_paths_are_under_the_root() {
+        assert_eq!(default_input(".", 4), "./day_4/input.txt");
+        assert_eq!(default_answers("/srv/aoc"), "/srv/aoc/answers.toml");
+    }
+
+    #[test]
+    fn unsolved_days_have_no_solver() {
+        assert!(solver(13).is_none());
+        assert!(profiler(13).is_none());
+        assert_eq!(solved_days(), vec!(1, 2, 3, 4, 5, 6));
+    }
+}
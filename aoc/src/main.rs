@@ -0,0 +1,149 @@
+mod alloc;
+mod args;
+mod days;
+mod profile;
+
+use alloc::CountingAllocator;
+use aoc_common::{input_hash, AnswerStore, Verdict};
+use args::{parse_args, Action, Days, USAGE};
+use profile::Summary;
+use std::env;
+use std::process;
+
+#[global_allocator]
+static ALLOCATOR : CountingAllocator = CountingAllocator;
+
+fn main() {
+    let args : Vec<String> = env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let root = command.root.as_deref().unwrap_or(".");
+    let answers_path = command.answers.clone().unwrap_or_else(|| days::default_answers(root));
+    let mut store = if command.action == Action::Run {
+        AnswerStore::new()
+    } else {
+        match AnswerStore::load(&answers_path) {
+            Ok(store) => store,
+            Err(error) => {
+                eprintln!("Error reading {}: {}", answers_path, error);
+                process::exit(1);
+            }
+        }
+    };
+
+    let inputs = match command.days {
+        Days::One(day) => vec!((day, command.input.clone())),
+        Days::All => days::solved_days().into_iter().map(|day| (day, Some(days::default_input(root, day)))).collect(),
+    };
+
+    if let Some(runs) = command.bench {
+        let mut summaries = Vec::new();
+        let mut failures = 0;
+        for (day, input) in inputs {
+            match bench_day(day, command.part, input.as_deref(), runs) {
+                Ok(day_summaries) => summaries.extend(day_summaries),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    failures += 1;
+                }
+            }
+        }
+
+        if command.json {
+            print!("{}", profile::json(&summaries));
+        } else {
+            for summary in summaries.iter() {
+                if let Some(ref answer) = summary.answer {
+                    println!("Day {} {}: {}", summary.day, summary.name, answer);
+                }
+            }
+            println!();
+            print!("{}", profile::table(&summaries));
+        }
+        process::exit(if failures > 0 { 1 } else { 0 });
+    }
+
+    let failures = inputs
+        .into_iter()
+        .map(|(day, input)| report(run_day(&command.action, day, command.part, input.as_deref(), &mut store)))
+        .filter(|ok| !ok)
+        .count();
+
+    if command.action == Action::Record {
+        if let Err(error) = store.save(&answers_path) {
+            eprintln!("Error writing {}: {}", answers_path, error);
+            process::exit(1);
+        }
+    }
+
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+fn run_day(action : &Action, day : u32, part : Option<u32>, input : Option<&str>, store : &mut AnswerStore) -> Result<(), String> {
+    let solve = days::solver(day).ok_or_else(|| format!("Day {} has no solution yet", day))?;
+    let input = aoc_common::read_input(input)
+        .map_err(|error| format!("Day {}: error reading input: {}", day, error))?;
+
+    let answers = solve(&input, part)
+        .map_err(|error| format!("Day {}: {}", day, error))?;
+
+    let hash = input_hash(&input);
+    let mut mismatches = 0;
+    for part in 1..=2 {
+        let answer = match answers.part(part) {
+            Some(answer) => answer,
+            None => continue,
+        };
+        match action {
+            Action::Run => println!("Day {} part {}: {}", day, part, answer),
+            Action::Record => {
+                store.record(day, part, &hash, answer);
+                println!("Day {} part {}: {} (recorded)", day, part, answer);
+            },
+            Action::Verify => match store.check(day, part, &hash, answer) {
+                Verdict::Match => println!("Day {} part {}: {} ok", day, part, answer),
+                Verdict::Unrecorded => println!("Day {} part {}: {} (no recorded answer)", day, part, answer),
+                Verdict::Mismatch { expected } => {
+                    println!("Day {} part {}: {} MISMATCH, expected {}", day, part, answer, expected);
+                    mismatches += 1;
+                },
+            },
+        }
+    }
+
+    if mismatches > 0 {
+        return Err(format!("Day {}: {} answer(s) differ from the recorded ones", day, mismatches));
+    }
+    Ok(())
+}
+
+fn bench_day(day : u32, part : Option<u32>, input : Option<&str>, runs : u32) -> Result<Vec<Summary>, String> {
+    let profile = days::profiler(day).ok_or_else(|| format!("Day {} has no solution yet", day))?;
+    let input = aoc_common::read_input(input)
+        .map_err(|error| format!("Day {}: error reading input: {}", day, error))?;
+
+    let runs = (0..runs)
+        .map(|_| profile(&input, part))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Day {}: {}", day, error))?;
+
+    Ok(profile::summarise(day, &runs))
+}
+
+fn report(result : Result<(), String>) -> bool {
+    match result {
+        Ok(()) => true,
+        Err(error) => {
+            eprintln!("{}", error);
+            false
+        }
+    }
+}
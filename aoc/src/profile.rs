@@ -0,0 +1,175 @@
+use crate::alloc::{self, Usage};
+use aoc_common::{answer_part, Puzzle, SolveError};
+use std::time::{Duration, Instant};
+
+// One timed phase of a puzzle: parsing its input or answering a part.
+#[derive(Debug)]
+pub struct Phase {
+    pub name : &'static str,
+    pub time : Duration,
+    pub usage : Usage,
+    pub answer : Option<String>,
+}
+
+pub type Profiler = fn(&str, Option<u32>) -> Result<Vec<Phase>, SolveError>;
+
+fn measure<T, F : FnOnce() -> T>(name : &'static str, f : F) -> (T, Phase) {
+    let start = Instant::now();
+    let (value, usage) = alloc::track(f);
+    let time = start.elapsed();
+    (value, Phase { name, time, usage, answer: None })
+}
+
+pub fn profile<P : Puzzle>(input : &str, part : Option<u32>) -> Result<Vec<Phase>, SolveError> {
+    let (parsed, phase) = measure("parse", || P::parse(input));
+    let parsed = parsed?;
+    let mut phases = vec!(phase);
+
+    if part != Some(2) {
+        let (answer, mut phase) = measure("part 1", || P::part1(&parsed));
+        phase.answer = Some(answer_part(1, &answer)?);
+        phases.push(phase);
+    }
+    if part != Some(1) {
+        let (answer, mut phase) = measure("part 2", || P::part2(&parsed));
+        phase.answer = Some(answer_part(2, &answer)?);
+        phases.push(phase);
+    }
+    Ok(phases)
+}
+
+// A phase's timings over several runs. Allocations and peak bytes are the
+// largest seen in any run.
+#[derive(Debug)]
+pub struct Summary {
+    pub day : u32,
+    pub name : &'static str,
+    pub runs : usize,
+    pub min : Duration,
+    pub median : Duration,
+    pub max : Duration,
+    pub allocations : usize,
+    pub peak_bytes : usize,
+    pub answer : Option<String>,
+}
+
+// Summarises repeated runs of a day. Every run must have the same phases.
+pub fn summarise(day : u32, runs : &[Vec<Phase>]) -> Vec<Summary> {
+    let first = match runs.first() {
+        Some(first) => first,
+        None => return Vec::new(),
+    };
+
+    first.iter().enumerate().map(|(i, phase)| {
+        let mut times : Vec<_> = runs.iter().map(|run| run[i].time).collect();
+        times.sort();
+        Summary {
+            day,
+            name: phase.name,
+            runs: runs.len(),
+            min: times[0],
+            median: median(&times),
+            max: times[times.len() - 1],
+            allocations: runs.iter().map(|run| run[i].usage.allocations).max().unwrap_or(0),
+            peak_bytes: runs.iter().map(|run| run[i].usage.peak_bytes).max().unwrap_or(0),
+            answer: phase.answer.clone(),
+        }
+    }).collect()
+}
+
+fn median(sorted : &[Duration]) -> Duration {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    }
+}
+
+pub fn table(summaries : &[Summary]) -> String {
+    let mut s = format!("{:>3}  {:<6}  {:>4}  {:>10}  {:>10}  {:>10}  {:>8}  {:>12}\n",
+        "day", "phase", "runs", "min", "median", "max", "allocs", "peak bytes");
+    for summary in summaries {
+        s.push_str(&format!("{:>3}  {:<6}  {:>4}  {:>10}  {:>10}  {:>10}  {:>8}  {:>12}\n",
+            summary.day, summary.name, summary.runs,
+            format_duration(summary.min), format_duration(summary.median), format_duration(summary.max),
+            summary.allocations, summary.peak_bytes));
+    }
+    s
+}
+
+pub fn json(summaries : &[Summary]) -> String {
+    let rows : Vec<_> = summaries.iter().map(|summary| {
+        let answer = match summary.answer {
+            Some(ref answer) => format!("\"{}\"", answer.replace('\\', "\\\\").replace('"', "\\\"")),
+            None => "null".to_string(),
+        };
+        format!("  {{\"day\": {}, \"phase\": \"{}\", \"runs\": {}, \"min_ns\": {}, \"median_ns\": {}, \"max_ns\": {}, \"allocations\": {}, \"peak_bytes\": {}, \"answer\": {}}}",
+            summary.day, summary.name, summary.runs,
+            summary.min.as_nanos(), summary.median.as_nanos(), summary.max.as_nanos(),
+            summary.allocations, summary.peak_bytes, answer)
+    }).collect();
+    format!("[\n{}\n]\n", rows.join(",\n"))
+}
+
+fn format_duration(d : Duration) -> String {
+    let ns = d.as_nanos();
+    if ns < 1_000 {
+        format!("{}ns", ns)
+    } else if ns < 1_000_000 {
+        format!("{:.1}µs", ns as f64 / 1e3)
+    } else if ns < 1_000_000_000 {
+        format!("{:.1}ms", ns as f64 / 1e6)
+    } else {
+        format!("{:.2}s", ns as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phase(name : &'static str, ms : u64, allocations : usize) -> Phase {
+        Phase {
+            name,
+            time: Duration::from_millis(ms),
+            usage: Usage { allocations, peak_bytes: allocations * 8 },
+            answer: None,
+        }
+    }
+
+    #[test]
+    fn runs_are_summarised_per_phase() {
+        let runs = vec!(
+            vec!(phase("parse", 3, 10), phase("part 1", 20, 1)),
+            vec!(phase("parse", 1, 12), phase("part 1", 40, 1)),
+            vec!(phase("parse", 2, 10), phase("part 1", 30, 1)),
+            vec!(phase("parse", 9, 10), phase("part 1", 10, 1)));
+        let summaries = summarise(4, &runs);
+
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].name, "parse");
+        assert_eq!(summaries[0].runs, 4);
+        assert_eq!(summaries[0].min, Duration::from_millis(1));
+        assert_eq!(summaries[0].median, Duration::from_micros(2500));
+        assert_eq!(summaries[0].max, Duration::from_millis(9));
+        assert_eq!(summaries[0].allocations, 12);
+        assert_eq!(summaries[0].peak_bytes, 96);
+        assert_eq!(summaries[1].median, Duration::from_millis(25));
+    }
+
+    #[test]
+    fn profiles_have_a_phase_per_requested_part() {
+        let phases = profile::<day_1::Day1>("+1\n-2\n+3\n+1\n", Some(1)).unwrap();
+        let names : Vec<_> = phases.iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!("parse", "part 1"));
+        assert_eq!(phases[1].answer, Some("3".to_string()));
+    }
+
+    #[test]
+    fn json_has_a_row_per_phase() {
+        let summaries = summarise(1, &[vec!(phase("parse", 1, 2))]);
+        assert_eq!(json(&summaries),
+            "[\n  {\"day\": 1, \"phase\": \"parse\", \"runs\": 1, \"min_ns\": 1000000, \"median_ns\": 1000000, \"max_ns\": 1000000, \"allocations\": 2, \"peak_bytes\": 16, \"answer\": null}\n]\n");
+    }
+}
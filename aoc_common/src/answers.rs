@@ -0,0 +1,195 @@
+use crate::{ParseError, ParseErrors};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Known-good answers, keyed by day, part and a hash of the input they were
+/// produced from. Stored on disk as a small TOML file:
+///
+/// ```toml
+/// [day_1.part_1]
+/// 9c4ab4c3d7b6e8f1 = "599"
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct AnswerStore {
+    answers : BTreeMap<(u32, u32), BTreeMap<String, String>>,
+}
+
+/// How a fresh answer compares with the recorded one.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Match,
+    Mismatch { expected : String },
+    Unrecorded,
+}
+
+impl AnswerStore {
+    pub fn new() -> AnswerStore {
+        AnswerStore::default()
+    }
+
+    /// Loads the store at `path`. A missing file is an empty store.
+    pub fn load<P : AsRef<Path>>(path : P) -> io::Result<AnswerStore> {
+        match fs::read_to_string(path) {
+            Ok(s) => AnswerStore::parse(&s).map_err(|errors| io::Error::new(io::ErrorKind::InvalidData, errors)),
+            Err(ref error) if error.kind() == io::ErrorKind::NotFound => Ok(AnswerStore::new()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save<P : AsRef<Path>>(&self, path : P) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    pub fn parse(s : &str) -> Result<AnswerStore, ParseErrors> {
+        let mut store = AnswerStore::new();
+        let mut section = None;
+        let mut errors = Vec::new();
+
+        for (n, line) in crate::numbered_lines(s) {
+            if line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') {
+                match parse_section(line) {
+                    Ok(day_part) => section = Some(day_part),
+                    Err(error) => errors.push(error.at_line(n)),
+                }
+                continue;
+            }
+            match (section, parse_entry(line)) {
+                (Some((day, part)), Ok((hash, answer))) => store.record(day, part, &hash, &answer),
+                (None, Ok(_)) => errors.push(ParseError::new(line, "a [day_N.part_M] section first").at_line(n)),
+                (_, Err(error)) => errors.push(error.at_line(n)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(store)
+        } else {
+            Err(ParseErrors(errors))
+        }
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut s = String::from("# Known-good answers, keyed by day, part and input hash.\n");
+        for ((day, part), answers) in self.answers.iter() {
+            s.push_str(&format!("\n[day_{}.part_{}]\n", day, part));
+            for (hash, answer) in answers.iter() {
+                s.push_str(&format!("{} = \"{}\"\n", hash, escape(answer)));
+            }
+        }
+        s
+    }
+
+    pub fn get(&self, day : u32, part : u32, hash : &str) -> Option<&str> {
+        self.answers.get(&(day, part)).and_then(|answers| answers.get(hash)).map(|answer| answer.as_str())
+    }
+
+    pub fn record(&mut self, day : u32, part : u32, hash : &str, answer : &str) {
+        self.answers.entry((day, part)).or_default().insert(hash.to_string(), answer.to_string());
+    }
+
+    pub fn check(&self, day : u32, part : u32, hash : &str, answer : &str) -> Verdict {
+        match self.get(day, part, hash) {
+            Some(expected) if expected == answer => Verdict::Match,
+            Some(expected) => Verdict::Mismatch { expected: expected.to_string() },
+            None => Verdict::Unrecorded,
+        }
+    }
+}
+
+/// A stable hash of the input's non-blank, trimmed lines, so line endings and
+/// trailing whitespace don't change it. 64-bit FNV-1a, as 16 hex digits.
+pub fn input_hash(input : &str) -> String {
+    let mut hash : u64 = 0xcbf2_9ce4_8422_2325;
+    for (i, (_, line)) in crate::numbered_lines(input).enumerate() {
+        let separator : &[u8] = if i > 0 { b"\n" } else { b"" };
+        for byte in separator.iter().chain(line.as_bytes()) {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+fn parse_section(line : &str) -> Result<(u32, u32), ParseError> {
+    let error = || ParseError::new(line, "a section like [day_1.part_2]");
+    let name = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')).ok_or_else(error)?;
+    let (day, part) = name.split_once('.').ok_or_else(error)?;
+    let day = day.strip_prefix("day_").and_then(|d| d.parse::<u32>().ok()).ok_or_else(error)?;
+    let part = part.strip_prefix("part_").and_then(|p| p.parse::<u32>().ok()).ok_or_else(error)?;
+    Ok((day, part))
+}
+
+fn parse_entry(line : &str) -> Result<(String, String), ParseError> {
+    let error = || ParseError::new(line, "an entry like 9c4ab4c3d7b6e8f1 = \"599\"");
+    let (hash, answer) = line.split_once('=').ok_or_else(error)?;
+    let hash = hash.trim();
+    if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(error());
+    }
+    let answer = answer.trim().strip_prefix('"').and_then(|a| a.strip_suffix('"')).ok_or_else(error)?;
+    Ok((hash.to_string(), unescape(answer)))
+}
+
+fn escape(s : &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn unescape(s : &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                result.push(escaped);
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_store_round_trips_through_toml() {
+        let mut store = AnswerStore::new();
+        store.record(1, 1, "00ff", "599");
+        store.record(1, 2, "00ff", "81204");
+        store.record(3, 2, "abcd", "#717 \"quoted\"");
+
+        assert_eq!(AnswerStore::parse(&store.to_toml()), Ok(store));
+    }
+
+    #[test]
+    fn answers_are_checked_against_the_recording() {
+        let mut store = AnswerStore::new();
+        store.record(1, 1, "00ff", "599");
+
+        assert_eq!(store.check(1, 1, "00ff", "599"), Verdict::Match);
+        assert_eq!(store.check(1, 1, "00ff", "600"), Verdict::Mismatch { expected: "599".to_string() });
+        assert_eq!(store.check(1, 1, "0000", "599"), Verdict::Unrecorded);
+        assert_eq!(store.check(1, 2, "00ff", "599"), Verdict::Unrecorded);
+    }
+
+    #[test]
+    fn malformed_stores_report_every_bad_line() {
+        let errors = AnswerStore::parse("00ff = \"1\"\n[day_1.part_1]\n00ff = 1\n[day_x]\n").unwrap_err();
+        let lines : Vec<_> = errors.0.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec!(Some(1), Some(3), Some(4)));
+    }
+
+    #[test]
+    fn the_input_hash_ignores_line_endings_and_padding() {
+        assert_eq!(input_hash("+1\n-2\n"), input_hash("  +1\r\n-2"));
+        assert_ne!(input_hash("+1\n-2\n"), input_hash("+1\n-3\n"));
+        assert_ne!(input_hash("ab\nc"), input_hash("a\nbc"));
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+    }
+}
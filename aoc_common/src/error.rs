@@ -0,0 +1,138 @@
+use std::error::Error;
+use std::fmt;
+
+/// A piece of input that couldn't be parsed. `line` is 1-based and is filled in
+/// by `parse_numbered` for parsers that only see a single line. `column` is the
+/// 1-based character within that line where the parser gave up, for parsers
+/// that know it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line : Option<usize>,
+    pub column : Option<usize>,
+    pub text : String,
+    pub expected : String,
+}
+
+impl ParseError {
+    pub fn new(text : &str, expected : &str) -> ParseError {
+        ParseError { line: None, column: None, text: text.to_string(), expected: expected.to_string() }
+    }
+
+    pub fn at_line(self, line : usize) -> ParseError {
+        ParseError { line: Some(line), ..self }
+    }
+
+    pub fn at_column(self, column : usize) -> ParseError {
+        ParseError { column: Some(column), ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {}, column {}: ", line, column)?,
+            (Some(line), None) => write!(f, "line {}: ", line)?,
+            (None, Some(column)) => write!(f, "column {}: ", column)?,
+            (None, None) => (),
+        }
+        write!(f, "expected {}, found '{}'", self.expected, self.text)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Every bad line found in an input, in the order they appear.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseErrors(pub Vec<ParseError>);
+
+impl From<ParseError> for ParseErrors {
+    fn from(error : ParseError) -> ParseErrors {
+        ParseErrors(vec!(error))
+    }
+}
+
+impl fmt::Display for ParseErrors {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseErrors {}
+
+/// Non-blank lines of `input`, trimmed and paired with their 1-based line number.
+pub fn numbered_lines(input : &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+/// Parses every line, carrying on past failures so that all of the bad lines are reported.
+pub fn parse_numbered<'a, T, I, F>(lines : I, parse : F) -> Result<Vec<T>, ParseErrors>
+    where I : IntoIterator<Item = (usize, &'a str)>,
+          F : Fn(&str) -> Result<T, ParseError>
+{
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    for (n, line) in lines {
+        match parse(line) {
+            Ok(value) => values.push(value),
+            Err(error) => errors.push(error.at_line(n)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(ParseErrors(errors))
+    }
+}
+
+/// Parses each non-blank line of `input` with `parse`.
+pub fn parse_lines<T, F>(input : &str, parse : F) -> Result<Vec<T>, ParseErrors>
+    where F : Fn(&str) -> Result<T, ParseError>
+{
+    parse_numbered(numbered_lines(input), parse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(s : &str) -> Result<i32, ParseError> {
+        s.parse().map_err(|_| ParseError::new(s, "a number"))
+    }
+
+    #[test]
+    fn every_bad_line_is_reported_with_its_line_number() {
+        let errors = parse_lines("1\nx\n\n3\ny", number).unwrap_err();
+        assert_eq!(errors.0, vec!(
+            ParseError::new("x", "a number").at_line(2),
+            ParseError::new("y", "a number").at_line(5)));
+    }
+
+    #[test]
+    fn good_lines_are_all_parsed() {
+        assert_eq!(parse_lines(" 1\n\n2 \n", number), Ok(vec!(1, 2)));
+    }
+
+    #[test]
+    fn errors_describe_what_was_expected() {
+        let error = ParseError::new("x", "a number").at_line(7);
+        assert_eq!(error.to_string(), "line 7: expected a number, found 'x'");
+    }
+
+    #[test]
+    fn errors_can_point_at_a_column() {
+        let error = ParseError::new("1x", "a number").at_column(2);
+        assert_eq!(error.to_string(), "column 2: expected a number, found '1x'");
+        assert_eq!(error.at_line(3).to_string(), "line 3, column 2: expected a number, found '1x'");
+    }
+}
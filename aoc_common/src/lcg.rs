@@ -0,0 +1,32 @@
+/// A fixed-seed linear congruential generator, for tests and benchmarks that
+/// need the same "random" data on every run and every commit.
+#[derive(Debug, Clone)]
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed : u64) -> Lcg {
+        Lcg(seed)
+    }
+
+    /// The next number in `0..bound`.
+    pub fn below(&mut self, bound : u64) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_gives_the_same_numbers() {
+        let numbers = |seed| {
+            let mut rng = Lcg::new(seed);
+            (0..5).map(|_| rng.below(100)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+        assert!(numbers(7).iter().all(|n| *n < 100));
+    }
+}
@@ -0,0 +1,86 @@
+//! Helpers shared by every day's solution: the `Puzzle` trait, loading the
+//! puzzle input from stdin or a file, splitting it into trimmed lines,
+//! reporting the lines that fail to parse and recording known-good answers.
+
+mod answers;
+mod error;
+mod lcg;
+mod puzzle;
+
+use std::fs::File;
+use std::io::{self, Read};
+
+pub use answers::{input_hash, AnswerStore, Verdict};
+pub use error::{numbered_lines, parse_lines, parse_numbered, ParseError, ParseErrors};
+pub use lcg::Lcg;
+pub use puzzle::{answer_part, run, solve, Answer, Answers, Puzzle, SolveError};
+
+/// Reads the whole input from `path`, or from stdin when no path (or `-`) is given.
+pub fn read_input(path : Option<&str>) -> io::Result<String> {
+    match path {
+        None | Some("-") => read_stdin(),
+        Some(filename) => read_file(filename),
+    }
+}
+
+pub fn read_stdin() -> io::Result<String> {
+    let mut s = String::new();
+    io::stdin().read_to_string(&mut s)?;
+    Ok(s)
+}
+
+pub fn read_file(filename : &str) -> io::Result<String> {
+    let mut f = File::open(filename)?;
+    let mut s = String::new();
+    f.read_to_string(&mut s)?;
+    Ok(s)
+}
+
+/// Splits `input` into lines with surrounding whitespace trimmed. Blank lines are dropped.
+pub fn lines(input : &str) -> Vec<String> {
+    input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect()
+}
+
+pub fn read_lines() -> Vec<String> {
+    match read_stdin() {
+        Ok(input) => lines(&input),
+        Err(error) => panic!("Error encountered: {}", error),
+    }
+}
+
+pub fn lines_from_file(filename : &str) -> Vec<String> {
+    match read_file(filename) {
+        Ok(input) => lines(&input),
+        Err(error) => panic!("Error reading {}: {}", filename, error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_are_trimmed() {
+        assert_eq!(lines("  +1\n-2  \r\n+3"), vec!("+1", "-2", "+3"));
+    }
+
+    #[test]
+    fn blank_lines_are_dropped() {
+        assert_eq!(lines("a\n\n  \nb\n"), vec!("a", "b"));
+    }
+
+    #[test]
+    fn an_empty_input_has_no_lines() {
+        assert!(lines("").is_empty());
+    }
+
+    #[test]
+    fn missing_files_are_an_error() {
+        assert!(read_input(Some("no/such/file.txt")).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::ParseErrors;
+use std::env;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::process;
+
+/// A day's solution. The input is parsed once and both parts are answered from it.
+/// Parsing reports every malformed line rather than stopping at the first.
+pub trait Puzzle {
+    const DAY : u32;
+
+    type Input;
+    type Part1 : Answer;
+    type Part2 : Answer;
+
+    fn parse(input : &str) -> Result<Self::Input, ParseErrors>;
+    fn part1(input : &Self::Input) -> Self::Part1;
+    fn part2(input : &Self::Input) -> Self::Part2;
+}
+
+/// What a part produces. Most parts always have an answer; a part that can fail
+/// on a well-formed input returns a Result, and a part that may find nothing
+/// returns an Option. Either way the missing answer is reported as a failure of
+/// the run rather than as an answer that could be recorded.
+pub trait Answer {
+    fn answer(&self) -> Result<String, String>;
+}
+
+macro_rules! display_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn answer(&self) -> Result<String, String> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+display_answer!(i32, i64, i128, u32, u64, u128, usize, String);
+
+impl<T : Answer, E : Display> Answer for Result<T, E> {
+    fn answer(&self) -> Result<String, String> {
+        match self {
+            Ok(value) => value.answer(),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+}
+
+impl<T : Answer> Answer for Option<T> {
+    fn answer(&self) -> Result<String, String> {
+        match self {
+            Some(value) => value.answer(),
+            None => Err("no answer".to_string()),
+        }
+    }
+}
+
+/// Why a puzzle couldn't be solved: either the input didn't parse, or a part
+/// failed on it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveError {
+    Parse(ParseErrors),
+    Part { part : u32, reason : String },
+}
+
+impl From<ParseErrors> for SolveError {
+    fn from(errors : ParseErrors) -> SolveError {
+        SolveError::Parse(errors)
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::Parse(errors) => write!(f, "the input has errors:\n{}", errors),
+            SolveError::Part { part, reason } => write!(f, "part {} failed: {}", part, reason),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// Turns a part's answer into its text, or the SolveError saying why it has none.
+pub fn answer_part<A : Answer>(part : u32, answer : &A) -> Result<String, SolveError> {
+    answer.answer().map_err(|reason| SolveError::Part { part, reason })
+}
+
+/// The answers from one run of a puzzle. A part that wasn't asked for is None.
+#[derive(Debug, PartialEq)]
+pub struct Answers {
+    pub part1 : Option<String>,
+    pub part2 : Option<String>,
+}
+
+impl Answers {
+    pub fn part(&self, part : u32) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Parses `input` and answers the requested part, or both when `part` is None.
+pub fn solve<P : Puzzle>(input : &str, part : Option<u32>) -> Result<Answers, SolveError> {
+    let parsed = P::parse(input)?;
+    Ok(Answers {
+        part1: if part != Some(2) { Some(answer_part(1, &P::part1(&parsed))?) } else { None },
+        part2: if part != Some(1) { Some(answer_part(2, &P::part2(&parsed))?) } else { None },
+    })
+}
+
+/// Entry point for a day's binary: reads the file named by the first argument
+/// (or stdin) and prints both answers.
+pub fn run<P : Puzzle>() {
+    let path = env::args().nth(1);
+    let input = match crate::read_input(path.as_deref()) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Error reading input: {}", error);
+            process::exit(1);
+        }
+    };
+
+    match solve::<P>(&input, None) {
+        Ok(answers) => {
+            println!("Part 1: {}", answers.part1.unwrap_or_default());
+            println!("Part 2: {}", answers.part2.unwrap_or_default());
+        },
+        Err(SolveError::Parse(errors)) => {
+            eprintln!("{}", errors);
+            process::exit(1);
+        },
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_values_are_answers() {
+        assert_eq!(42u32.answer(), Ok("42".to_string()));
+        assert_eq!("fgij".to_string().answer(), Ok("fgij".to_string()));
+    }
+
+    #[test]
+    fn a_failed_part_is_not_an_answer() {
+        let failed : Result<i64, String> = Err("overflowed".to_string());
+        assert_eq!(answer_part(2, &failed), Err(SolveError::Part { part: 2, reason: "overflowed".to_string() }));
+        assert_eq!(answer_part(2, &failed).unwrap_err().to_string(), "part 2 failed: overflowed");
+        assert_eq!(answer_part(1, &Ok::<i64, String>(-3)), Ok("-3".to_string()));
+    }
+
+    #[test]
+    fn a_part_that_finds_nothing_is_not_an_answer() {
+        assert_eq!(answer_part(2, &None::<usize>), Err(SolveError::Part { part: 2, reason: "no answer".to_string() }));
+        assert_eq!(answer_part(2, &Some(4usize)), Ok("4".to_string()));
+    }
+}
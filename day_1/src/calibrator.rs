@@ -0,0 +1,219 @@
+use crate::number::{Drift, Overflow};
+use crate::parse_drift;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::io::{self, BufRead};
+
+// Something noteworthy that happened when a drift was applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<T = i64> {
+    NewMin(T),
+    NewMax(T),
+    SignChange(T),
+    FirstRepeat(T),
+}
+
+// Applies drifts one at a time, keeping running statistics instead of the drifts
+// themselves. To spot the first repeat it does keep every frequency seen so
+// far, which grows by one per drift until a repeat turns up and may never stop
+// growing if one doesn't; `without_repeats` skips that for constant memory.
+// Frequencies are kept in any Drift type, as with `drift` and `first_repeat`.
+#[derive(Debug)]
+pub struct Calibrator<T : Drift = i64> {
+    frequency : T,
+    min : T,
+    max : T,
+    drifts : u64,
+    sign_changes : u64,
+    last_sign : Ordering,
+    first_repeat : Option<T>,
+    // None once the first repeat is found, or when repeats aren't tracked.
+    seen : Option<HashSet<T>>,
+}
+
+impl<T : Drift> Default for Calibrator<T> {
+    fn default() -> Calibrator<T> {
+        Calibrator::new()
+    }
+}
+
+impl<T : Drift> Calibrator<T> {
+    pub fn new() -> Calibrator<T> {
+        let mut calibrator = Calibrator::without_repeats();
+        calibrator.seen = Some(HashSet::from([T::zero()]));
+        calibrator
+    }
+
+    // A calibrator that never reports a FirstRepeat, and so keeps nothing that
+    // grows with the number of drifts.
+    pub fn without_repeats() -> Calibrator<T> {
+        Calibrator {
+            frequency: T::zero(),
+            min: T::zero(),
+            max: T::zero(),
+            drifts: 0,
+            sign_changes: 0,
+            last_sign: Ordering::Equal,
+            first_repeat: None,
+            seen: None,
+        }
+    }
+
+    pub fn frequency(&self) -> &T {
+        &self.frequency
+    }
+
+    pub fn min(&self) -> &T {
+        &self.min
+    }
+
+    pub fn max(&self) -> &T {
+        &self.max
+    }
+
+    pub fn drifts(&self) -> u64 {
+        self.drifts
+    }
+
+    // How many times the frequency has gone from positive to negative or back.
+    // Passing through zero doesn't count as a sign of its own.
+    pub fn sign_changes(&self) -> u64 {
+        self.sign_changes
+    }
+
+    pub fn first_repeat(&self) -> Option<&T> {
+        self.first_repeat.as_ref()
+    }
+
+    // Applies one drift. A frequency too big for T is an Overflow, and leaves
+    // the calibrator as it was before the drift.
+    pub fn feed(&mut self, drift : T) -> Result<Vec<Event<T>>, Overflow> {
+        let mut events = Vec::new();
+        self.frequency = self.frequency.checked_add(&drift).ok_or(Overflow { drift: self.drifts as usize + 1 })?;
+        self.drifts += 1;
+
+        if self.frequency < self.min {
+            self.min = self.frequency.clone();
+            events.push(Event::NewMin(self.frequency.clone()));
+        }
+        if self.frequency > self.max {
+            self.max = self.frequency.clone();
+            events.push(Event::NewMax(self.frequency.clone()));
+        }
+
+        let sign = self.frequency.cmp(&T::zero());
+        if sign != Ordering::Equal {
+            if self.last_sign != Ordering::Equal && sign != self.last_sign {
+                self.sign_changes += 1;
+                events.push(Event::SignChange(self.frequency.clone()));
+            }
+            self.last_sign = sign;
+        }
+
+        if let Some(seen) = &mut self.seen {
+            if !seen.insert(self.frequency.clone()) {
+                self.first_repeat = Some(self.frequency.clone());
+                self.seen = None;
+                events.push(Event::FirstRepeat(self.frequency.clone()));
+            }
+        }
+        Ok(events)
+    }
+
+    // Feeds every drift from `reader`, a line at a time, calling `on_event` as
+    // things happen. Blank lines are skipped; a bad drift or an overflow stops
+    // the read with an InvalidData error saying which line it was on.
+    pub fn read<R : BufRead, F : FnMut(&Calibrator<T>, Event<T>)>(&mut self, reader : R, mut on_event : F) -> io::Result<()> {
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let drift = parse_drift(line).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.at_line(i + 1)))?;
+            let events = self.feed(drift)
+                .map_err(|overflow| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", i + 1, overflow)))?;
+            for event in events {
+                on_event(self, event);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics_are_kept_as_drifts_arrive() {
+        let mut calibrator = Calibrator::<i64>::new();
+        calibrator.read("+3\n-5\n\n+4\n-1\n".as_bytes(), |_, _| ()).unwrap();
+        assert_eq!(*calibrator.frequency(), 1);
+        assert_eq!(*calibrator.min(), -2);
+        assert_eq!(*calibrator.max(), 3);
+        assert_eq!(calibrator.drifts(), 4);
+        assert_eq!(calibrator.sign_changes(), 2);
+        assert_eq!(calibrator.first_repeat(), None);
+    }
+
+    #[test]
+    fn events_are_reported_in_order() {
+        let mut calibrator = Calibrator::<i64>::new();
+        let mut events = Vec::new();
+        calibrator.read("+1\n-2\n+3\n+1\n+1\n-2".as_bytes(), |_, event| events.push(event)).unwrap();
+        assert_eq!(events, vec!(
+            Event::NewMax(1),
+            Event::NewMin(-1),
+            Event::SignChange(-1),
+            Event::NewMax(2),
+            Event::SignChange(2),
+            Event::NewMax(3),
+            Event::NewMax(4),
+            Event::FirstRepeat(2)));
+    }
+
+    #[test]
+    fn only_the_first_repeat_is_reported() {
+        let mut calibrator = Calibrator::new();
+        let events : Vec<_> = [1, -1, 1, -1].iter().flat_map(|drift| calibrator.feed(*drift).unwrap()).collect();
+        assert_eq!(events, vec!(Event::NewMax(1), Event::FirstRepeat(0)));
+        assert_eq!(calibrator.first_repeat(), Some(&0));
+    }
+
+    #[test]
+    fn repeats_can_be_left_untracked() {
+        let mut calibrator = Calibrator::without_repeats();
+        let events : Vec<_> = [1, -1, 1, -1].iter().flat_map(|drift| calibrator.feed(*drift).unwrap()).collect();
+        assert_eq!(events, vec!(Event::NewMax(1)));
+        assert_eq!(calibrator.first_repeat(), None);
+        assert_eq!(calibrator.drifts(), 4);
+    }
+
+    #[test]
+    fn drifts_can_be_wider_than_32_bits() {
+        let mut calibrator = Calibrator::<i64>::new();
+        calibrator.read("+4294967296\n-8589934592\n".as_bytes(), |_, _| ()).unwrap();
+        assert_eq!(*calibrator.frequency(), -4294967296);
+        assert_eq!(*calibrator.max(), 4294967296);
+        assert_eq!(calibrator.sign_changes(), 1);
+    }
+
+    #[test]
+    fn an_overflow_stops_the_read() {
+        let mut calibrator = Calibrator::<i32>::new();
+        let error = calibrator.read("+2147483647\n+1\n".as_bytes(), |_, _| ()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "line 2: the frequency overflowed at drift 2");
+        assert_eq!(*calibrator.frequency(), i32::MAX);
+    }
+
+    #[test]
+    fn bad_drifts_stop_the_read() {
+        let mut calibrator = Calibrator::<i64>::new();
+        let error = calibrator.read("+1\n\nfoo\n+2".as_bytes(), |_, _| ()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "line 3: expected a drift like +1 or -2, found 'foo'");
+        assert_eq!(*calibrator.frequency(), 1);
+    }
+}
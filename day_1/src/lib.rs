@@ -0,0 +1,183 @@
+pub mod calibrator;
+pub mod number;
+
+use aoc_common::{ParseError, ParseErrors, Puzzle};
+use number::{Drift, Overflow};
+use std::collections::{HashMap, HashSet};
+
+pub struct Day1;
+
+impl Puzzle for Day1 {
+    const DAY : u32 = 1;
+
+    type Input = Vec<i64>;
+    // An overflowing frequency isn't an answer, and neither is a frequency that
+    // never repeats, so both fail the run.
+    type Part1 = Result<i64, Overflow>;
+    type Part2 = Result<Option<i64>, Overflow>;
+
+    fn parse(input : &str) -> Result<Vec<i64>, ParseErrors> {
+        parse_drifts(input)
+    }
+
+    fn part1(drifts : &Vec<i64>) -> Result<i64, Overflow> {
+        drift(drifts)
+    }
+
+    fn part2(drifts : &Vec<i64>) -> Result<Option<i64>, Overflow> {
+        first_repeat(drifts)
+    }
+}
+
+pub fn parse_drift<T : Drift>(s : &str) -> Result<T, ParseError> {
+    s.trim().parse::<T>().map_err(|_| ParseError::new(s, "a drift like +1 or -2"))
+}
+
+pub fn parse_drifts<T : Drift>(input : &str) -> Result<Vec<T>, ParseErrors> {
+    aoc_common::parse_lines(input, parse_drift)
+}
+
+pub fn drift<T : Drift>(drifts : &[T]) -> Result<T, Overflow> {
+    let mut freq = T::zero();
+    for (i, drift) in drifts.iter().enumerate() {
+        freq = freq.checked_add(drift).ok_or(Overflow { drift: i + 1 })?;
+    }
+    Ok(freq)
+}
+
+// The first frequency reached twice, or None if the frequencies never repeat.
+//
+// After k full passes the frequency reached by step i is prefix[i] + k * total,
+// so two steps can only ever meet if their prefix sums are congruent modulo the
+// total drift. Within each residue class, sorted in the direction of the drift,
+// each prefix sum is next caught up with by its neighbour; the earliest of those
+// catch-ups is the answer. A gap between two prefix sums that doesn't fit in T
+// is reported as an Overflow too.
+pub fn first_repeat<T : Drift>(drifts : &[T]) -> Result<Option<T>, Overflow> {
+    let mut freq = T::zero();
+    let mut prefixes = Vec::with_capacity(drifts.len());
+    let mut seen = HashSet::new();
+    for (i, drift) in drifts.iter().enumerate() {
+        if !seen.insert(freq.clone()) {
+            return Ok(Some(freq));
+        }
+        prefixes.push(freq.clone());
+        freq = freq.checked_add(drift).ok_or(Overflow { drift: i + 1 })?;
+    }
+
+    let total = freq;
+    if total == T::zero() {
+        return Ok(if drifts.is_empty() { None } else { Some(total) });
+    }
+
+    let mut classes : HashMap<T, Vec<(T, usize)>> = HashMap::new();
+    for (i, prefix) in prefixes.into_iter().enumerate() {
+        let residue = prefix.checked_rem_euclid(&total).ok_or(Overflow { drift: i + 1 })?;
+        classes.entry(residue).or_default().push((prefix, i));
+    }
+
+    // Ordered by (passes, step), which is the order the repeats happen in.
+    let mut earliest : Option<(T, usize, T)> = None;
+    for class in classes.values_mut() {
+        if total.is_positive() {
+            class.sort_by(|a, b| a.0.cmp(&b.0));
+        } else {
+            class.sort_by(|a, b| b.0.cmp(&a.0));
+        }
+        for pair in class.windows(2) {
+            let ((from, step), (to, _)) = (&pair[0], &pair[1]);
+            let passes = to.checked_sub(from).and_then(|gap| gap.checked_div(&total)).ok_or(Overflow { drift: step + 1 })?;
+            let candidate = (passes, *step, to.clone());
+            if earliest.as_ref().is_none_or(|best| candidate < *best) {
+                earliest = Some(candidate);
+            }
+        }
+    }
+    Ok(earliest.map(|(_, _, freq)| freq))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drift_is_the_sum_of_all_changes() {
+        let drifts = Day1::parse("+1\n-2\n+3\n+1\n").unwrap();
+        assert_eq!(Day1::part1(&drifts), Ok(3));
+    }
+
+    #[test]
+    fn drift_overflow_is_an_error() {
+        assert_eq!(drift(&[i32::MAX, -1, 1, 1]), Err(Overflow { drift: 4 }));
+        assert_eq!(drift(&[i64::from(i32::MAX), -1, 1, 1]), Ok(2147483648));
+        assert_eq!(drift(&[i128::MAX, i128::MIN]), Ok(-1));
+        assert_eq!(Day1::part1(&vec!(i64::MIN, -1)), Err(Overflow { drift: 2 }));
+    }
+
+    #[test]
+    fn drifts_can_be_parsed_as_any_width() {
+        assert_eq!(parse_drifts::<i32>("+2147483648"), Err(ParseError::new("+2147483648", "a drift like +1 or -2").at_line(1).into()));
+        assert_eq!(parse_drifts::<i64>("+2147483648\n-1"), Ok(vec!(2147483648, -1)));
+        assert_eq!(parse_drifts::<i128>("+170141183460469231731687303715884105727"), Ok(vec!(i128::MAX)));
+    }
+
+    #[test]
+    fn first_repeat_may_take_several_passes() {
+        assert_eq!(first_repeat(&[1, -2, 3, 1]), Ok(Some(2)));
+        assert_eq!(first_repeat(&[1, -1]), Ok(Some(0)));
+        assert_eq!(first_repeat(&[3, 3, 4, -2, -4]), Ok(Some(10)));
+        assert_eq!(first_repeat(&[-6, 3, 8, 5, -6]), Ok(Some(5)));
+        assert_eq!(first_repeat(&[7, 7, -2, -7, -4]), Ok(Some(14)));
+        assert_eq!(Day1::part2(&Day1::parse("+1\n-2\n+3\n+1").unwrap()), Ok(Some(2)));
+    }
+
+    #[test]
+    fn first_repeat_gives_up_when_nothing_repeats() {
+        assert_eq!(first_repeat(&[1, 1]), Ok(None));
+        assert_eq!(first_repeat(&[-3, 1]), Ok(None));
+        assert_eq!(first_repeat::<i64>(&[]), Ok(None));
+        assert_eq!(Day1::part2(&vec!(1, 1)), Ok(None));
+    }
+
+    #[test]
+    fn first_repeat_finds_the_earliest_catch_up() {
+        assert_eq!(first_repeat(&[1, 2, -4, 7, -5]), Ok(Some(1)));
+        assert_eq!(first_repeat(&[10, -9, 3, -7, -1]), Ok(Some(-3)));
+        assert_eq!(first_repeat(&[-2, 9, -4, -6]), Ok(Some(0)));
+        assert_eq!(first_repeat(&[-7, 4, 2, -1, 3]), Ok(Some(-2)));
+        assert_eq!(first_repeat(&[6, -2, -5, 4, -1, -4]), Ok(Some(4)));
+        assert_eq!(first_repeat(&[0]), Ok(Some(0)));
+        assert_eq!(first_repeat(&[5, -3]), Ok(None));
+    }
+
+    #[test]
+    fn first_repeat_handles_a_huge_drift_in_one_pass() {
+        let drifts = [i32::MAX, 1, -2, i32::MIN];
+        assert_eq!(first_repeat(&drifts), Err(Overflow { drift: 2 }));
+        let drifts : Vec<i64> = drifts.iter().map(|d| i64::from(*d)).collect();
+        assert_eq!(first_repeat(&drifts), Ok(Some(2147483646)));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn big_integers_never_overflow() {
+        use num_bigint::BigInt;
+
+        let drifts : Vec<BigInt> = parse_drifts("+170141183460469231731687303715884105727\n+170141183460469231731687303715884105727\n-3").unwrap();
+        assert_eq!(drift(&drifts).unwrap().to_string(), "340282366920938463463374607431768211451");
+        let drifts : Vec<BigInt> = parse_drifts("+7\n+7\n-2\n-7\n-4").unwrap();
+        assert_eq!(first_repeat(&drifts), Ok(Some(BigInt::from(14))));
+        let drifts : Vec<BigInt> = parse_drifts("-6\n+3\n+8\n+5\n-6").unwrap();
+        assert_eq!(first_repeat(&drifts), Ok(Some(BigInt::from(5))));
+        let drifts : Vec<BigInt> = parse_drifts("-5\n+3").unwrap();
+        assert_eq!(first_repeat(&drifts), Ok(None));
+    }
+
+    #[test]
+    fn bad_drifts_are_reported_by_line() {
+        let errors = Day1::parse("+1\nfoo\n-2\n3x").unwrap_err();
+        assert_eq!(errors.0, vec!(
+            ParseError::new("foo", "a drift like +1 or -2").at_line(2),
+            ParseError::new("3x", "a drift like +1 or -2").at_line(4)));
+    }
+}
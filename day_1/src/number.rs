@@ -0,0 +1,95 @@
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
+
+// The numeric type drifts and frequencies are kept in. Every operation is
+// checked so that a frequency too big for the type is an Overflow rather than
+// a silently wrapped answer.
+pub trait Drift : Clone + Ord + Hash + FromStr + fmt::Display + fmt::Debug {
+    fn zero() -> Self;
+    fn is_positive(&self) -> bool;
+    fn checked_add(&self, other : &Self) -> Option<Self>;
+    fn checked_sub(&self, other : &Self) -> Option<Self>;
+    fn checked_div(&self, other : &Self) -> Option<Self>;
+    fn checked_rem_euclid(&self, other : &Self) -> Option<Self>;
+}
+
+macro_rules! primitive_drift {
+    ($($t:ty),*) => {
+        $(
+            impl Drift for $t {
+                fn zero() -> $t {
+                    0
+                }
+
+                fn is_positive(&self) -> bool {
+                    *self > 0
+                }
+
+                fn checked_add(&self, other : &$t) -> Option<$t> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other : &$t) -> Option<$t> {
+                    <$t>::checked_sub(*self, *other)
+                }
+
+                fn checked_div(&self, other : &$t) -> Option<$t> {
+                    <$t>::checked_div(*self, *other)
+                }
+
+                fn checked_rem_euclid(&self, other : &$t) -> Option<$t> {
+                    <$t>::checked_rem_euclid(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+primitive_drift!(i32, i64, i128);
+
+#[cfg(feature = "bigint")]
+impl Drift for num_bigint::BigInt {
+    fn zero() -> num_bigint::BigInt {
+        num_bigint::BigInt::from(0)
+    }
+
+    fn is_positive(&self) -> bool {
+        self.sign() == num_bigint::Sign::Plus
+    }
+
+    fn checked_add(&self, other : &num_bigint::BigInt) -> Option<num_bigint::BigInt> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other : &num_bigint::BigInt) -> Option<num_bigint::BigInt> {
+        Some(self - other)
+    }
+
+    fn checked_div(&self, other : &num_bigint::BigInt) -> Option<num_bigint::BigInt> {
+        if other.sign() == num_bigint::Sign::NoSign { None } else { Some(self / other) }
+    }
+
+    fn checked_rem_euclid(&self, other : &num_bigint::BigInt) -> Option<num_bigint::BigInt> {
+        if other.sign() == num_bigint::Sign::NoSign {
+            return None;
+        }
+        let rem = self % other;
+        Some(if rem.sign() == num_bigint::Sign::Minus { rem + num_bigint::BigInt::from(other.magnitude().clone()) } else { rem })
+    }
+}
+
+// The frequency no longer fitted in the drift type. `drift` is the 1-based
+// position of the drift that was being applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overflow {
+    pub drift : usize,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the frequency overflowed at drift {}", self.drift)
+    }
+}
+
+impl std::error::Error for Overflow {}
@@ -0,0 +1,42 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day_11::{FuelCells, SummedArea};
+use std::hint::black_box;
+
+const SERIAL : i32 = 3214;
+
+fn best_3x3(c : &mut Criterion) {
+    let mut group = c.benchmark_group("day_11/best_3x3");
+    for size in [50, 100, 300] {
+        let cells = FuelCells::with_size(SERIAL, size);
+        group.bench_with_input(BenchmarkId::new("brute_force", size), &cells, |b, cells| {
+            b.iter(|| black_box(cells.best_square(3)))
+        });
+        group.bench_with_input(BenchmarkId::new("summed_area", size), &cells, |b, cells| {
+            b.iter(|| black_box(SummedArea::new(cells).best_square(3)))
+        });
+    }
+    group.finish();
+}
+
+fn best_any_size(c : &mut Criterion) {
+    let mut group = c.benchmark_group("day_11/best_any_size");
+    group.sample_size(10);
+    for size in [25, 50, 100] {
+        let cells = FuelCells::with_size(SERIAL, size);
+        group.bench_with_input(BenchmarkId::new("brute_force", size), &cells, |b, cells| {
+            b.iter(|| {
+                let best = (1..=size)
+                    .map(|square| cells.best_square(square))
+                    .max_by_key(|(power, _, _)| *power);
+                black_box(best)
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("summed_area", size), &cells, |b, cells| {
+            b.iter(|| black_box(SummedArea::new(cells).best_any_square()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, best_3x3, best_any_size);
+criterion_main!(benches);
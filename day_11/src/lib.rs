@@ -0,0 +1,142 @@
+// The fuel cell grid from day11_spec.rb. Coordinates are 1-based, as in the puzzle.
+
+pub fn power_level(x : i32, y : i32, serial : i32) -> i32 {
+    let rack_id = x + 10;
+    (rack_id * y + serial) * rack_id / 100 % 10 - 5
+}
+
+pub struct FuelCells {
+    size : usize,
+    power_levels : Vec<i32>,
+}
+
+impl FuelCells {
+    pub fn new(serial : i32) -> FuelCells {
+        FuelCells::with_size(serial, 300)
+    }
+
+    pub fn with_size(serial : i32, size : usize) -> FuelCells {
+        let mut power_levels = Vec::with_capacity(size * size);
+        for y in 1..=size {
+            for x in 1..=size {
+                power_levels.push(power_level(x as i32, y as i32, serial));
+            }
+        }
+        FuelCells { size, power_levels }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn power_level(&self, x : usize, y : usize) -> i32 {
+        self.power_levels[(y - 1) * self.size + (x - 1)]
+    }
+
+    // Brute force: adds up every cell of the square with its top left at x, y.
+    pub fn power_sum(&self, x : usize, y : usize, square : usize) -> i32 {
+        let mut sum = 0;
+        for iy in 0..square {
+            for ix in 0..square {
+                sum += self.power_level(x + ix, y + iy);
+            }
+        }
+        sum
+    }
+
+    // The (power, x, y) of the most powerful square of the given size.
+    pub fn best_square(&self, square : usize) -> (i32, usize, usize) {
+        best_square(self.size, square, |x, y| self.power_sum(x, y, square))
+    }
+}
+
+// A summed-area table over a grid, so any square's power is four lookups.
+pub struct SummedArea {
+    size : usize,
+    sums : Vec<i32>,
+}
+
+impl SummedArea {
+    pub fn new(cells : &FuelCells) -> SummedArea {
+        let size = cells.size();
+        let width = size + 1;
+        let mut sums = vec!(0; width * width);
+        for y in 1..=size {
+            for x in 1..=size {
+                sums[y * width + x] = cells.power_level(x, y)
+                    + sums[(y - 1) * width + x]
+                    + sums[y * width + x - 1]
+                    - sums[(y - 1) * width + x - 1];
+            }
+        }
+        SummedArea { size, sums }
+    }
+
+    pub fn power_sum(&self, x : usize, y : usize, square : usize) -> i32 {
+        let width = self.size + 1;
+        let (x1, y1, x2, y2) = (x - 1, y - 1, x - 1 + square, y - 1 + square);
+        self.sums[y2 * width + x2] - self.sums[y1 * width + x2] - self.sums[y2 * width + x1] + self.sums[y1 * width + x1]
+    }
+
+    pub fn best_square(&self, square : usize) -> (i32, usize, usize) {
+        best_square(self.size, square, |x, y| self.power_sum(x, y, square))
+    }
+
+    // The (power, x, y, size) of the most powerful square of any size.
+    pub fn best_any_square(&self) -> (i32, usize, usize, usize) {
+        (1..=self.size)
+            .map(|square| {
+                let (power, x, y) = self.best_square(square);
+                (power, x, y, square)
+            })
+            .fold((i32::MIN, 0, 0, 0), |best, candidate| if candidate.0 > best.0 { candidate } else { best })
+    }
+}
+
+fn best_square<F : Fn(usize, usize) -> i32>(size : usize, square : usize, power_sum : F) -> (i32, usize, usize) {
+    let mut best = (i32::MIN, 0, 0);
+    for y in 1..=(size - square + 1) {
+        for x in 1..=(size - square + 1) {
+            let power = power_sum(x, y);
+            if power > best.0 {
+                best = (power, x, y);
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn power_levels_match_the_examples() {
+        assert_eq!(power_level(3, 5, 8), 4);
+        assert_eq!(power_level(122, 79, 57), -5);
+        assert_eq!(power_level(217, 196, 39), 0);
+        assert_eq!(power_level(101, 153, 71), 4);
+    }
+
+    #[test]
+    fn the_best_3x3_square_is_found() {
+        assert_eq!(FuelCells::new(18).best_square(3), (29, 33, 45));
+        assert_eq!(FuelCells::new(42).best_square(3), (30, 21, 61));
+    }
+
+    #[test]
+    fn summed_areas_agree_with_brute_force() {
+        let cells = FuelCells::new(18);
+        let table = SummedArea::new(&cells);
+        assert_eq!(table.power_sum(90, 269, 16), 113);
+        assert_eq!(table.power_sum(90, 269, 16), cells.power_sum(90, 269, 16));
+        assert_eq!(table.best_square(3), cells.best_square(3));
+        assert_eq!(table.power_sum(1, 1, 300), cells.power_sum(1, 1, 300));
+    }
+
+    #[test]
+    fn the_best_square_of_any_size_is_found() {
+        assert_eq!(SummedArea::new(&FuelCells::new(18)).best_any_square(), (113, 90, 269, 16));
+        assert_eq!(SummedArea::new(&FuelCells::new(42)).best_any_square(), (119, 232, 251, 12));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::nearby::Distance;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+// A distance between IDs. For the tree's pruning to be right it must be a
+// true metric: zero only for equal IDs, symmetric, and obeying the triangle
+// inequality.
+pub trait Metric {
+    fn distance(&self, a : &str, b : &str) -> usize;
+}
+
+impl Metric for Distance {
+    fn distance(&self, a : &str, b : &str) -> usize {
+        self.within(a, b, usize::MAX).expect("there's no limit")
+    }
+}
+
+impl<F : Fn(&str, &str) -> usize> Metric for F {
+    fn distance(&self, a : &str, b : &str) -> usize {
+        self(a, b)
+    }
+}
+
+struct Node {
+    id : String,
+    children : BTreeMap<usize, Node>,
+}
+
+// A Burkhard-Keller tree of IDs. Each child sits under the distance from its
+// parent, so a query only has to visit children whose distance is within its
+// radius of the query's distance to the parent.
+pub struct BkTree<M : Metric> {
+    metric : M,
+    root : Option<Node>,
+    len : usize,
+}
+
+impl<M : Metric> BkTree<M> {
+    pub fn new(metric : M) -> BkTree<M> {
+        BkTree { metric, root: None, len: 0 }
+    }
+
+    pub fn from_ids<S : AsRef<str>>(ids : &[S], metric : M) -> BkTree<M> {
+        let mut tree = BkTree::new(metric);
+        for id in ids {
+            tree.insert(id.as_ref());
+        }
+        tree
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Adds `id`, returning false if it was already there.
+    pub fn insert(&mut self, id : &str) -> bool {
+        let mut node = match self.root {
+            Some(ref mut root) => root,
+            None => {
+                self.root = Some(Node { id: id.to_string(), children: BTreeMap::new() });
+                self.len += 1;
+                return true;
+            },
+        };
+        loop {
+            let d = self.metric.distance(&node.id, id);
+            if d == 0 {
+                return false;
+            }
+            match node.children.entry(d) {
+                Entry::Occupied(child) => node = child.into_mut(),
+                Entry::Vacant(slot) => {
+                    slot.insert(Node { id: id.to_string(), children: BTreeMap::new() });
+                    self.len += 1;
+                    return true;
+                },
+            }
+        }
+    }
+
+    // Every ID no more than `radius` from `query`, nearest first.
+    pub fn within(&self, query : &str, radius : usize) -> Vec<(&str, usize)> {
+        let mut found = Vec::new();
+        let mut pending : Vec<&Node> = self.root.iter().collect();
+        while let Some(node) = pending.pop() {
+            let d = self.metric.distance(&node.id, query);
+            if d <= radius {
+                found.push((node.id.as_str(), d));
+            }
+            pending.extend(node.children.range(d.saturating_sub(radius)..=d.saturating_add(radius)).map(|(_, child)| child));
+        }
+        found.sort_by_key(|(id, d)| (*d, *id));
+        found
+    }
+
+    // The `k` IDs nearest to `query`, nearest first. Ties are broken by ID.
+    pub fn nearest(&self, query : &str, k : usize) -> Vec<(&str, usize)> {
+        let mut found : Vec<(&str, usize)> = Vec::new();
+        if k == 0 {
+            return found;
+        }
+        let mut pending : Vec<&Node> = self.root.iter().collect();
+        while let Some(node) = pending.pop() {
+            let d = self.metric.distance(&node.id, query);
+            let position = found.partition_point(|(id, fd)| (*fd, *id) < (d, node.id.as_str()));
+            if position < k {
+                found.insert(position, (node.id.as_str(), d));
+                found.truncate(k);
+            }
+            // Once k IDs are known, only children that could beat the worst of them matter.
+            let radius = if found.len() == k { found[k - 1].1 } else { usize::MAX };
+            pending.extend(node.children.range(d.saturating_sub(radius)..=d.saturating_add(radius)).map(|(_, child)| child));
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDS : [&str; 7] = ["abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz"];
+
+    fn brute_force<M : Metric>(metric : &M, query : &str) -> Vec<(&'static str, usize)> {
+        let mut all : Vec<_> = IDS.iter().map(|id| (*id, metric.distance(id, query))).collect();
+        all.sort_by_key(|(id, d)| (*d, *id));
+        all
+    }
+
+    #[test]
+    fn within_finds_every_id_in_the_radius() {
+        let tree = BkTree::from_ids(&IDS, Distance::Hamming);
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.within("fghij", 1), vec!(("fghij", 0), ("fguij", 1)));
+        for radius in 0..=6 {
+            let expected : Vec<_> = brute_force(&Distance::Hamming, "abxyz").into_iter().filter(|(_, d)| *d <= radius).collect();
+            assert_eq!(tree.within("abxyz", radius), expected);
+        }
+        assert_eq!(tree.within("abxyz", usize::MAX), brute_force(&Distance::Hamming, "abxyz"));
+    }
+
+    #[test]
+    fn nearest_finds_the_closest_ids() {
+        let tree = BkTree::from_ids(&IDS, Distance::Levenshtein);
+        assert_eq!(tree.nearest("fgij", 2), vec!(("fghij", 1), ("fguij", 1)));
+        for k in 0..=8 {
+            let expected : Vec<_> = brute_force(&Distance::Levenshtein, "kxlmo").into_iter().take(k).collect();
+            assert_eq!(tree.nearest("kxlmo", k), expected);
+        }
+    }
+
+    #[test]
+    fn duplicates_are_only_inserted_once() {
+        let mut tree = BkTree::new(Distance::Hamming);
+        assert!(tree.is_empty());
+        assert!(tree.insert("abc"));
+        assert!(tree.insert("abcd"));
+        assert!(!tree.insert("abc"));
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree.within("abc", 0), vec!(("abc", 0)));
+        assert_eq!(tree.within("abc", 1), vec!(("abc", 0), ("abcd", 1)));
+    }
+
+    #[test]
+    fn any_metric_can_be_plugged_in() {
+        // The discrete metric: every pair of different IDs is 1 apart.
+        let same_or_not = |a : &str, b : &str| usize::from(a != b);
+        let mut tree = BkTree::from_ids(&["abc", "abd", "xyz"], same_or_not);
+        assert!(!tree.insert("abc"));
+        assert_eq!(tree.nearest("abd", 2), vec!(("abd", 0), ("abc", 1)));
+        assert_eq!(tree.within("abq", 1), vec!(("abc", 1), ("abd", 1), ("xyz", 1)));
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::{BTreeMap, HashMap};
+use unicode_segmentation::UnicodeSegmentation;
+
+// What counts as a single letter of an ID.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Alphabet {
+    Bytes,
+    Chars,
+    Graphemes,
+}
+
+impl Alphabet {
+    pub fn letters<'a>(&self, id : &'a str) -> Vec<&'a [u8]> {
+        match self {
+            Alphabet::Bytes => id.as_bytes().chunks(1).collect(),
+            Alphabet::Chars => id.char_indices().map(|(i, c)| &id.as_bytes()[i..i + c.len_utf8()]).collect(),
+            Alphabet::Graphemes => id.graphemes(true).map(|g| g.as_bytes()).collect(),
+        }
+    }
+}
+
+// How many times each letter appears in `id`.
+pub fn letter_counts(id : &str, alphabet : Alphabet) -> HashMap<&[u8], usize> {
+    let mut counts = HashMap::new();
+    for letter in alphabet.letters(id) {
+        *counts.entry(letter).or_insert(0) += 1;
+    }
+    counts
+}
+
+// For each multiplicity, the number of IDs with some letter appearing exactly
+// that many times.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checksum {
+    counts : BTreeMap<usize, u64>,
+}
+
+impl Checksum {
+    pub fn new<S : AsRef<str>>(ids : &[S], multiplicities : &[usize], alphabet : Alphabet) -> Checksum {
+        let mut counts : BTreeMap<usize, u64> = multiplicities.iter().map(|m| (*m, 0)).collect();
+        for id in ids {
+            let letters = letter_counts(id.as_ref(), alphabet);
+            for (multiplicity, count) in counts.iter_mut() {
+                if letters.values().any(|n| n == multiplicity) {
+                    *count += 1;
+                }
+            }
+        }
+        Checksum { counts }
+    }
+
+    pub fn count(&self, multiplicity : usize) -> Option<u64> {
+        self.counts.get(&multiplicity).copied()
+    }
+
+    pub fn counts(&self) -> impl Iterator<Item = (usize, u64)> + '_ {
+        self.counts.iter().map(|(m, n)| (*m, *n))
+    }
+
+    pub fn product(&self) -> u64 {
+        self.counts.values().product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDS : [&str; 7] = ["abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab"];
+
+    #[test]
+    fn each_multiplicity_is_counted() {
+        let checksum = Checksum::new(&IDS, &[2, 3, 4], Alphabet::Chars);
+        assert_eq!(checksum.counts().collect::<Vec<_>>(), vec!((2, 4), (3, 3), (4, 0)));
+        assert_eq!(checksum.count(2), Some(4));
+        assert_eq!(checksum.count(5), None);
+        assert_eq!(checksum.product(), 0);
+        assert_eq!(Checksum::new(&IDS, &[2, 3], Alphabet::Bytes).product(), 12);
+    }
+
+    #[test]
+    fn letters_depend_on_the_alphabet() {
+        // "e" followed by a combining acute accent, twice.
+        let id = "e\u{301}e\u{301}";
+        assert_eq!(Alphabet::Bytes.letters(id).len(), 6);
+        assert_eq!(Alphabet::Chars.letters(id).len(), 4);
+        assert_eq!(Alphabet::Graphemes.letters(id).len(), 2);
+
+        assert_eq!(Checksum::new(&[id], &[2, 4], Alphabet::Chars).counts().collect::<Vec<_>>(), vec!((2, 1), (4, 0)));
+        assert_eq!(Checksum::new(&["é"], &[2], Alphabet::Chars).count(2), Some(0));
+        assert_eq!(Checksum::new(&["é"], &[2], Alphabet::Bytes).count(2), Some(0));
+        assert_eq!(Checksum::new(&["\u{301}\u{301}"], &[2], Alphabet::Bytes).count(2), Some(1));
+    }
+}
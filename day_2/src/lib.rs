@@ -0,0 +1,109 @@
+pub mod bk_tree;
+pub mod frequency;
+pub mod nearby;
+
+use aoc_common::{ParseErrors, Puzzle};
+use frequency::{letter_counts, Alphabet, Checksum};
+
+/*
+def str_diffs(str1, str2) 
+  str1.chars.zip(str2.chars).map { |a, b| a != b ? 1 : 0 }
+end
+
+def diff_map(lines, str)
+  lines.map { |line| str_diffs(str, line) }
+end
+
+while !lines.empty? do
+  str = lines.pop
+  dm = diff_map(lines, str).select { |d| d.sum == 1 }
+  if !dm.empty?
+    diff = dm.first
+    puts str.tap { |s| s.slice!(diff.index(1)) }
+    exit
+  end
+end
+*/
+
+pub struct Day2;
+
+impl Puzzle for Day2 {
+    const DAY : u32 = 2;
+
+    type Input = Vec<String>;
+    type Part1 = u64;
+    type Part2 = Option<String>;
+
+    fn parse(input : &str) -> Result<Vec<String>, ParseErrors> {
+        Ok(aoc_common::lines(input))
+    }
+
+    fn part1(lines : &Vec<String>) -> u64 {
+        checksum(lines)
+    }
+
+    fn part2(lines : &Vec<String>) -> Option<String> {
+        common_letters(lines)
+    }
+}
+
+pub fn checksum(lines : &[String]) -> u64 {
+    Checksum::new(lines, &[2, 3], Alphabet::Chars).product()
+}
+
+pub fn common_letters(lines : &[String]) -> Option<String> {
+    nearby::one_apart(lines).first().map(|pair| pair.common_letters(lines))
+}
+
+// 1 where the strings differ and 0 where they agree. Letters past the end of
+// the shorter string all count as differences.
+pub fn str_diffs(str1 : &str, str2 : &str) -> Vec<i32> {
+    let mut chars1 = str1.chars();
+    let mut chars2 = str2.chars();
+    let mut diffs = Vec::new();
+    loop {
+        match (chars1.next(), chars2.next()) {
+            (None, None) => return diffs,
+            (a, b) => diffs.push(if a != b { 1 } else { 0 }),
+        }
+    }
+}
+
+pub fn diff_map(lines : &[String], s : &str) -> Vec<Vec<i32>> {
+    lines.iter().map(|l| str_diffs(s, l)).collect()
+}
+
+pub fn count_chars(str : &str) -> (bool, bool) {
+    let counts = letter_counts(str, Alphabet::Chars);
+    (counts.values().any(|n| *n == 2), counts.values().any(|n| *n == 3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_multiplies_the_twos_and_threes() {
+        let ids = Day2::parse("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab\n").unwrap();
+        assert_eq!(Day2::part1(&ids), 12);
+    }
+
+    #[test]
+    fn str_diffs_counts_extra_letters_as_differences() {
+        assert_eq!(str_diffs("abcd", "abxd"), vec!(0, 0, 1, 0));
+        assert_eq!(str_diffs("abc", "abcde"), vec!(0, 0, 0, 1, 1));
+        assert_eq!(str_diffs("abcde", "ab"), vec!(0, 0, 1, 1, 1));
+    }
+
+    #[test]
+    fn common_letters_drops_the_differing_character() {
+        let ids = Day2::parse("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz\n").unwrap();
+        assert_eq!(Day2::part2(&ids), Some("fgij".to_string()));
+    }
+
+    #[test]
+    fn ids_that_are_all_far_apart_have_no_answer() {
+        let ids = Day2::parse("abc\nxyz\n").unwrap();
+        assert_eq!(Day2::part2(&ids), None);
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+// Two IDs, by index, that differ only at `position` (counted in chars).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pair {
+    pub first : usize,
+    pub second : usize,
+    pub position : usize,
+}
+
+impl Pair {
+    // The letters the two IDs have in common: either one without `position`.
+    pub fn common_letters(&self, ids : &[impl AsRef<str>]) -> String {
+        ids[self.first].as_ref().chars().enumerate().filter(|(i, _)| *i != self.position).map(|(_, c)| c).collect()
+    }
+}
+
+const BASE : u64 = 0x0100_0000_01b3;
+
+// Every pair of IDs that differ in exactly one position, in index order.
+//
+// Each ID is hashed once for every position with that position masked out,
+// using prefix and suffix hashes so each masked hash is O(1). IDs that collide
+// on the same masked position are then compared directly, which weeds out
+// both hash collisions and exact duplicates.
+pub fn one_apart<S : AsRef<str>>(ids : &[S]) -> Vec<Pair> {
+    let chars : Vec<Vec<char>> = ids.iter().map(|id| id.as_ref().chars().collect()).collect();
+
+    let mut buckets : HashMap<(usize, usize, u64, u64), Vec<usize>> = HashMap::new();
+    for (index, id) in chars.iter().enumerate() {
+        let len = id.len();
+        let mut prefixes = vec!(0u64; len + 1);
+        for (i, c) in id.iter().enumerate() {
+            prefixes[i + 1] = prefixes[i].wrapping_mul(BASE).wrapping_add(u64::from(*c) + 1);
+        }
+        let mut suffixes = vec!(0u64; len + 1);
+        for (i, c) in id.iter().enumerate().rev() {
+            suffixes[i] = suffixes[i + 1].wrapping_mul(BASE).wrapping_add(u64::from(*c) + 1);
+        }
+        for position in 0..len {
+            buckets.entry((len, position, prefixes[position], suffixes[position + 1])).or_default().push(index);
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for ((_, position, _, _), indices) in buckets.iter() {
+        for (i, first) in indices.iter().enumerate() {
+            for second in &indices[i + 1..] {
+                if differ_only_at(&chars[*first], &chars[*second], *position) {
+                    pairs.push(Pair { first: *first, second: *second, position: *position });
+                }
+            }
+        }
+    }
+    pairs.sort_by_key(|pair| (pair.first, pair.second));
+    pairs
+}
+
+fn differ_only_at(a : &[char], b : &[char], position : usize) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).enumerate().all(|(i, (x, y))| (i == position) != (x == y))
+}
+
+// How far apart two IDs are. Both are true metrics over IDs of any length, so
+// they can be used to build a BkTree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distance {
+    // Letters that differ position by position, as str_diffs counts them: each
+    // letter past the end of the shorter ID is one more difference.
+    Hamming,
+    // Single-letter insertions, deletions and substitutions.
+    Levenshtein,
+}
+
+impl Distance {
+    // The distance between `a` and `b`, or None if it's more than `limit`.
+    pub fn within(&self, a : &str, b : &str, limit : usize) -> Option<usize> {
+        let a : Vec<char> = a.chars().collect();
+        let b : Vec<char> = b.chars().collect();
+        match self {
+            Distance::Hamming => hamming(&a, &b, limit),
+            Distance::Levenshtein => edit_table(&a, &b, limit).map(|table| table[a.len()][b.len()]),
+        }
+    }
+
+    // The letters `a` and `b` share, in order: the unchanged positions for
+    // Hamming, or the letters kept by a cheapest edit for Levenshtein.
+    pub fn common_letters(&self, a : &str, b : &str) -> String {
+        let a : Vec<char> = a.chars().collect();
+        let b : Vec<char> = b.chars().collect();
+        match self {
+            Distance::Hamming => a.iter().zip(b.iter()).filter(|(x, y)| x == y).map(|(x, _)| x).collect(),
+            Distance::Levenshtein => {
+                let table = edit_table(&a, &b, usize::MAX).expect("there's no limit");
+                let (mut i, mut j) = (a.len(), b.len());
+                let mut common = Vec::new();
+                while i > 0 && j > 0 {
+                    if a[i - 1] == b[j - 1] && table[i][j] == table[i - 1][j - 1] {
+                        common.push(a[i - 1]);
+                        i -= 1;
+                        j -= 1;
+                    } else if table[i][j] == table[i - 1][j - 1] + 1 {
+                        i -= 1;
+                        j -= 1;
+                    } else if table[i][j] == table[i - 1][j] + 1 {
+                        i -= 1;
+                    } else {
+                        j -= 1;
+                    }
+                }
+                common.iter().rev().collect()
+            },
+        }
+    }
+}
+
+// Two IDs, by index, that are `distance` apart, with the letters they share.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub first : usize,
+    pub second : usize,
+    pub distance : usize,
+    pub common_letters : String,
+}
+
+// Every pair of IDs no more than `limit` apart, in index order.
+pub fn within<S : AsRef<str>>(ids : &[S], limit : usize, distance : Distance) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for (first, a) in ids.iter().enumerate() {
+        for (second, b) in ids.iter().enumerate().skip(first + 1) {
+            let (a, b) = (a.as_ref(), b.as_ref());
+            if let Some(d) = distance.within(a, b, limit) {
+                matches.push(Match { first, second, distance: d, common_letters: distance.common_letters(a, b) });
+            }
+        }
+    }
+    matches
+}
+
+fn hamming(a : &[char], b : &[char], limit : usize) -> Option<usize> {
+    let mut distance = a.len().abs_diff(b.len());
+    if distance > limit {
+        return None;
+    }
+    for (x, y) in a.iter().zip(b.iter()) {
+        if x != y {
+            distance += 1;
+            if distance > limit {
+                return None;
+            }
+        }
+    }
+    Some(distance)
+}
+
+// The Wagner-Fischer table of edit distances between prefixes of `a` and `b`,
+// or None as soon as a whole row is over `limit`.
+fn edit_table(a : &[char], b : &[char], limit : usize) -> Option<Vec<Vec<usize>>> {
+    if a.len().abs_diff(b.len()) > limit {
+        return None;
+    }
+    let mut table = vec!(vec!(0; b.len() + 1); a.len() + 1);
+    for (j, cell) in table[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        table[i][0] = i;
+        for j in 1..=b.len() {
+            let substitution = table[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            table[i][j] = substitution.min(table[i - 1][j] + 1).min(table[i][j - 1] + 1);
+        }
+        if table[i].iter().all(|d| *d > limit) {
+            return None;
+        }
+    }
+    if table[a.len()][b.len()] > limit {
+        return None;
+    }
+    Some(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_pair_one_letter_apart_is_found() {
+        let ids = ["abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz", "fghix", "abcde"];
+        assert_eq!(one_apart(&ids), vec!(
+            Pair { first: 1, second: 4, position: 2 },
+            Pair { first: 1, second: 7, position: 4 }));
+        assert_eq!(one_apart(&ids)[0].common_letters(&ids), "fgij");
+    }
+
+    #[test]
+    fn ids_of_other_lengths_and_duplicates_are_not_pairs() {
+        assert!(one_apart(&["abc", "abcd", "abc", "xyzw"]).is_empty());
+        assert_eq!(one_apart(&["héllo", "hèllo"]), vec!(Pair { first: 0, second: 1, position: 1 }));
+    }
+
+    #[test]
+    fn hamming_distance_counts_extra_letters() {
+        let ids = ["abcde", "axcye", "abcd", "fghij", "abxyz"];
+        assert_eq!(within(&ids, 2, Distance::Hamming), vec!(
+            Match { first: 0, second: 1, distance: 2, common_letters: "ace".to_string() },
+            Match { first: 0, second: 2, distance: 1, common_letters: "abcd".to_string() }));
+        assert_eq!(within(&ids, 3, Distance::Hamming).len(), 6);
+        assert_eq!(Distance::Hamming.within("abc", "abc", 0), Some(0));
+        assert_eq!(Distance::Hamming.within("abc", "abd", 0), None);
+        assert_eq!(Distance::Hamming.within("abc", "abcd", 5), Some(1));
+        assert_eq!(Distance::Hamming.within("abc", "abcdef", 2), None);
+    }
+
+    #[test]
+    fn levenshtein_distance_pairs_any_lengths() {
+        assert_eq!(Distance::Levenshtein.within("kitten", "sitting", 3), Some(3));
+        assert_eq!(Distance::Levenshtein.within("kitten", "sitting", 2), None);
+        assert_eq!(Distance::Levenshtein.within("", "abc", 3), Some(3));
+        assert_eq!(Distance::Levenshtein.common_letters("kitten", "sitting"), "ittn");
+
+        let ids = ["abcde", "abde", "xabcde", "fghij"];
+        assert_eq!(within(&ids, 1, Distance::Levenshtein), vec!(
+            Match { first: 0, second: 1, distance: 1, common_letters: "abde".to_string() },
+            Match { first: 0, second: 2, distance: 1, common_letters: "abcde".to_string() }));
+    }
+}
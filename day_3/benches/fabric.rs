@@ -0,0 +1,55 @@
+use aoc_common::Lcg;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day_3::{isolated_claim, sweep, Claim, Fabric, Rect};
+use std::hint::black_box;
+
+// `count` claims of up to 30x30 inches scattered over a 1000x1000 fabric.
+fn claims(count : usize) -> Vec<Claim> {
+    let mut rng = Lcg::new(count as u64);
+    let mut next = |bound| rng.below(bound) as i32;
+    (0..count)
+        .map(|i| Claim::new(format!("#{}", i + 1), next(970), next(970), next(29) + 1, next(29) + 1))
+        .collect()
+}
+
+fn fill(c : &mut Criterion) {
+    let mut group = c.benchmark_group("day_3/fill_and_count");
+    for count in [100, 1000, 5000] {
+        let claims = claims(count);
+        group.bench_with_input(BenchmarkId::from_parameter(count), &claims, |b, claims| {
+            b.iter(|| {
+                let mut fabric = Fabric::new(1000, 1000);
+                for claim in claims.iter() {
+                    fabric.fill(claim);
+                }
+                black_box(fabric.count_multiples())
+            })
+        });
+    }
+    group.finish();
+}
+
+fn sweep_line(c : &mut Criterion) {
+    let mut group = c.benchmark_group("day_3/sweep_line");
+    for count in [100, 1000, 5000] {
+        let rects : Vec<Rect> = claims(count).iter().map(|claim| claim.rect().clone()).collect();
+        group.bench_with_input(BenchmarkId::from_parameter(count), &rects, |b, rects| {
+            b.iter(|| black_box(sweep::overlap_area(rects)))
+        });
+    }
+    group.finish();
+}
+
+fn isolated(c : &mut Criterion) {
+    let mut group = c.benchmark_group("day_3/isolated_claim");
+    for count in [100, 500, 1000] {
+        let claims = claims(count);
+        group.bench_with_input(BenchmarkId::from_parameter(count), &claims, |b, claims| {
+            b.iter(|| black_box(isolated_claim(claims).map(|claim| claim.id().len())))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, fill, sweep_line, isolated);
+criterion_main!(benches);
@@ -0,0 +1,190 @@
+use crate::index::ClaimIndex;
+use crate::sweep;
+use crate::{Claim, Rect};
+
+// How one claim fares against the rest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimReport {
+    pub id : String,
+    pub area : u128,
+    // The part of the claim that some other claim also covers.
+    pub overlapped_area : u128,
+    pub conflicts : Vec<String>,
+}
+
+// A group of claims joined by overlaps, and the area claimed more than once within it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cluster {
+    pub ids : Vec<String>,
+    pub contested_area : u128,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub claims : Vec<ClaimReport>,
+    // Largest first; claims that overlap nothing aren't clusters.
+    pub clusters : Vec<Cluster>,
+}
+
+pub fn analyse(claims : &[Claim]) -> Analysis {
+    let graph = ClaimIndex::new(claims).overlap_graph();
+
+    let reports = claims.iter().zip(graph.iter()).map(|(claim, neighbours)| {
+        let overlaps : Vec<Rect> = neighbours.iter().filter_map(|n| claim.rect().intersection(claims[*n].rect())).collect();
+        ClaimReport {
+            id: claim.id().to_string(),
+            area: claim.rect().area(),
+            overlapped_area: sweep::coverage(&overlaps).covered,
+            conflicts: neighbours.iter().map(|n| claims[*n].id().to_string()).collect(),
+        }
+    }).collect();
+
+    let mut clusters : Vec<Cluster> = components(&graph)
+        .into_iter()
+        .filter(|members| members.len() > 1)
+        .map(|members| {
+            let rects : Vec<Rect> = members.iter().map(|m| claims[*m].rect().clone()).collect();
+            Cluster {
+                ids: members.iter().map(|m| claims[*m].id().to_string()).collect(),
+                contested_area: sweep::overlap_area(&rects),
+            }
+        })
+        .collect();
+    clusters.sort_by(|a, b| b.ids.len().cmp(&a.ids.len()).then(b.contested_area.cmp(&a.contested_area)));
+
+    Analysis { claims: reports, clusters }
+}
+
+// The connected components of an adjacency list, each in index order.
+fn components(graph : &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut seen = vec!(false; graph.len());
+    let mut components = Vec::new();
+    for start in 0..graph.len() {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut members = vec!(start);
+        let mut pending = vec!(start);
+        while let Some(node) = pending.pop() {
+            for next in graph[node].iter() {
+                if !seen[*next] {
+                    seen[*next] = true;
+                    members.push(*next);
+                    pending.push(*next);
+                }
+            }
+        }
+        members.sort_unstable();
+        components.push(members);
+    }
+    components
+}
+
+impl Analysis {
+    pub fn table(&self) -> String {
+        let mut s = format!("{:<8}  {:>8}  {:>10}  {}\n", "claim", "area", "overlapped", "conflicts");
+        for report in self.claims.iter() {
+            s.push_str(&format!("{:<8}  {:>8}  {:>10}  {}\n",
+                report.id, report.area, report.overlapped_area, report.conflicts.join(" ")));
+        }
+        s.push_str(&format!("\n{:<8}  {:>8}  {:>10}  {}\n", "cluster", "claims", "contested", "ids"));
+        for (i, cluster) in self.clusters.iter().enumerate() {
+            s.push_str(&format!("{:<8}  {:>8}  {:>10}  {}\n",
+                i + 1, cluster.ids.len(), cluster.contested_area, cluster.ids.join(" ")));
+        }
+        s
+    }
+
+    pub fn json(&self) -> String {
+        let claims : Vec<_> = self.claims.iter().map(|report| {
+            format!("    {{\"id\": {}, \"area\": {}, \"overlapped_area\": {}, \"conflicts\": {}}}",
+                json_string(&report.id), report.area, report.overlapped_area, json_strings(&report.conflicts))
+        }).collect();
+        let clusters : Vec<_> = self.clusters.iter().map(|cluster| {
+            format!("    {{\"ids\": {}, \"contested_area\": {}}}", json_strings(&cluster.ids), cluster.contested_area)
+        }).collect();
+        format!("{{\n  \"claims\": [\n{}\n  ],\n  \"clusters\": [\n{}\n  ]\n}}\n", claims.join(",\n"), clusters.join(",\n"))
+    }
+}
+
+fn json_string(s : &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn json_strings(strings : &[String]) -> String {
+    let quoted : Vec<_> = strings.iter().map(|s| json_string(s)).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims() -> Vec<Claim> {
+        vec!(
+            Claim::new("#1".to_string(), 1, 3, 4, 4),
+            Claim::new("#2".to_string(), 3, 1, 4, 4),
+            Claim::new("#3".to_string(), 5, 5, 2, 2),
+            Claim::new("#4".to_string(), 20, 20, 3, 3),
+            Claim::new("#5".to_string(), 22, 22, 3, 3),
+            Claim::new("#6".to_string(), 2, 4, 2, 2))
+    }
+
+    #[test]
+    fn each_claim_reports_its_overlaps() {
+        let analysis = analyse(&claims());
+        assert_eq!(analysis.claims[0], ClaimReport {
+            id: "#1".to_string(),
+            area: 16,
+            overlapped_area: 7,
+            conflicts: vec!("#2".to_string(), "#6".to_string()),
+        });
+        assert_eq!(analysis.claims[2].overlapped_area, 0);
+        assert!(analysis.claims[2].conflicts.is_empty());
+        assert_eq!(analysis.claims[5].overlapped_area, 4);
+    }
+
+    #[test]
+    fn areas_too_big_for_i32_are_reported_in_full() {
+        let analysis = analyse(&[
+            Claim::new("#1".to_string(), 0, 0, 70000, 70000),
+            Claim::new("#2".to_string(), 10000, 10000, i32::MAX, i32::MAX)]);
+        assert_eq!(analysis.claims[0].area, 4_900_000_000);
+        assert_eq!(analysis.claims[0].overlapped_area, 3_600_000_000);
+        assert_eq!(analysis.claims[1].area, 4_611_686_014_132_420_609);
+        assert_eq!(analysis.clusters[0].contested_area, 3_600_000_000);
+    }
+
+    #[test]
+    fn clusters_are_connected_claims_largest_first() {
+        let analysis = analyse(&claims());
+        assert_eq!(analysis.clusters, vec!(
+            Cluster { ids: vec!("#1".to_string(), "#2".to_string(), "#6".to_string()), contested_area: 7 },
+            Cluster { ids: vec!("#4".to_string(), "#5".to_string()), contested_area: 1 }));
+    }
+
+    #[test]
+    fn the_analysis_is_printed_as_a_table_or_json() {
+        let analysis = analyse(&claims()[3..5]);
+        assert_eq!(analysis.table(), "\
+claim         area  overlapped  conflicts
+#4               9           1  #5
+#5               9           1  #4
+
+cluster     claims   contested  ids
+1                2           1  #4 #5
+");
+        assert_eq!(analysis.json(), "\
+{
+  \"claims\": [
+    {\"id\": \"#4\", \"area\": 9, \"overlapped_area\": 1, \"conflicts\": [\"#5\"]},
+    {\"id\": \"#5\", \"area\": 9, \"overlapped_area\": 1, \"conflicts\": [\"#4\"]}
+  ],
+  \"clusters\": [
+    {\"ids\": [\"#4\", \"#5\"], \"contested_area\": 1}
+  ]
+}
+");
+    }
+}
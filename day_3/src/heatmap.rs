@@ -0,0 +1,148 @@
+use crate::index::ClaimIndex;
+use crate::{Claim, Fabric, Rect};
+
+// The colour for an inch (or claim) covered `count` times, when the most any
+// is covered is `max`: black when unclaimed, blue when claimed once, then
+// shading from yellow to red as the overlaps pile up.
+pub fn heat(count : u32, max : u32) -> (u8, u8, u8) {
+    match count {
+        0 => (0, 0, 0),
+        1 => (40, 60, 160),
+        _ => {
+            let t = if max > 2 { f64::from(count - 2) / f64::from(max - 2) } else { 1.0 };
+            (255, (220.0 * (1.0 - t.min(1.0))).round() as u8, 0)
+        },
+    }
+}
+
+// A binary PPM (P6) of the claim counts inside `viewport`, one pixel per inch
+// and `scale` pixels on a side. Inches outside the fabric, or past the edge of
+// i32, are unclaimed.
+pub fn to_ppm(fabric : &Fabric, viewport : &Rect, scale : usize) -> Vec<u8> {
+    let width = viewport.width().max(0) as usize * scale;
+    let height = viewport.height().max(0) as usize * scale;
+    let max = fabric.max_count();
+
+    let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    ppm.reserve(width * height * 3);
+    let count = |x : i64, y : i64| match (i32::try_from(x), i32::try_from(y)) {
+        (Ok(x), Ok(y)) => fabric.count(x, y),
+        _ => 0,
+    };
+    for py in 0..height {
+        let y = i64::from(viewport.top()) + (py / scale) as i64;
+        for px in 0..width {
+            let x = i64::from(viewport.left()) + (px / scale) as i64;
+            let (r, g, b) = heat(count(x, y), max);
+            ppm.extend_from_slice(&[r, g, b]);
+        }
+    }
+    ppm
+}
+
+// An SVG of the outlines of the claims that show inside `viewport`, each
+// coloured by how many other claims it overlaps.
+pub fn to_svg(claims : &[Claim], viewport : &Rect) -> String {
+    let degrees : Vec<u32> = ClaimIndex::new(claims).overlap_graph().iter().map(|n| n.len() as u32).collect();
+    let max = degrees.iter().copied().max().unwrap_or(0) + 1;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+        viewport.left(), viewport.top(), viewport.width(), viewport.height());
+    for (claim, degree) in claims.iter().zip(degrees.iter()) {
+        if !claim.rect().intersects_with(viewport) {
+            continue;
+        }
+        let (r, g, b) = heat(degree + 1, max);
+        let rect = claim.rect();
+        svg.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#{:02x}{:02x}{:02x}\" stroke-width=\"0.2\"><title>{} overlaps {}</title></rect>\n",
+            rect.left(), rect.top(), rect.width(), rect.height(), r, g, b, escape(claim.id()), degree));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn escape(s : &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Vec<Claim> {
+        vec!(
+            Claim::new("#1".to_string(), 1, 3, 4, 4),
+            Claim::new("#2".to_string(), 3, 1, 4, 4),
+            Claim::new("#3".to_string(), 5, 5, 2, 2))
+    }
+
+    #[test]
+    fn the_fabric_keeps_claim_counts() {
+        let mut fabric = Fabric::new(8, 8);
+        for claim in example().iter().chain(example().iter()) {
+            fabric.fill(claim);
+        }
+        assert_eq!(fabric.count(0, 0), 0);
+        assert_eq!(fabric.count(1, 3), 2);
+        assert_eq!(fabric.count(3, 3), 4);
+        assert_eq!(fabric.count(-1, 3), 0);
+        assert_eq!(fabric.max_count(), 4);
+        assert_eq!(fabric.state(3, 3), crate::FabricState::Multiple);
+    }
+
+    #[test]
+    fn the_ppm_covers_the_viewport() {
+        let mut fabric = Fabric::new(8, 8);
+        for claim in example().iter() {
+            fabric.fill(claim);
+        }
+        let ppm = to_ppm(&fabric, &Rect::new(2, 3, 3, 2), 2);
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 6 * 4 * 3);
+        // Row y=3 is one claim at x=2, then two claims at x=3 and x=4.
+        assert_eq!(&pixels[..3], &[40, 60, 160]);
+        assert_eq!(&pixels[3..6], &[40, 60, 160]);
+        assert_eq!(&pixels[6..9], &[255, 0, 0]);
+    }
+
+    #[test]
+    fn the_viewport_can_go_past_the_fabric() {
+        let fabric = Fabric::new(2, 2);
+        let ppm = to_ppm(&fabric, &Rect::new(-5, -5, 10, 1), 1);
+        assert_eq!(ppm.len(), b"P6\n10 1\n255\n".len() + 30);
+        assert!(ppm[b"P6\n10 1\n255\n".len()..].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn the_viewport_can_go_past_the_edge_of_i32() {
+        let fabric = Fabric::new(2, 2);
+        let ppm = to_ppm(&fabric, &Rect::new(i32::MAX - 1, i32::MAX - 1, 4, 3), 2);
+        let header = b"P6\n8 6\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 8 * 6 * 3);
+        assert!(ppm[header.len()..].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn the_svg_outlines_claims_in_the_viewport() {
+        let svg = to_svg(&example(), &Rect::new(0, 0, 5, 5));
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 5 5\">"));
+        assert!(svg.contains("<rect x=\"1\" y=\"3\" width=\"4\" height=\"4\" fill=\"none\" stroke=\"#ff0000\""));
+        assert!(svg.contains("<title>#2 overlaps 1</title>"));
+        assert!(!svg.contains("#3"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn heat_runs_from_blue_through_yellow_to_red() {
+        assert_eq!(heat(0, 5), (0, 0, 0));
+        assert_eq!(heat(1, 5), (40, 60, 160));
+        assert_eq!(heat(2, 5), (255, 220, 0));
+        assert_eq!(heat(5, 5), (255, 0, 0));
+        assert_eq!(heat(2, 2), (255, 0, 0));
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{Claim, Rect};
+
+const CAPACITY : usize = 8;
+
+struct Node {
+    bounds : Rect,
+    // Indexes into the claims for a leaf, or into the nodes for a branch.
+    entries : Vec<usize>,
+    leaf : bool,
+}
+
+// A static R-tree over a set of claims, packed with Sort-Tile-Recursive so
+// each node holds up to CAPACITY neighbouring rects. A query only descends
+// into nodes whose bounds meet the rect it's looking for.
+pub struct ClaimIndex<'a> {
+    claims : &'a [Claim],
+    nodes : Vec<Node>,
+    root : Option<usize>,
+}
+
+impl<'a> ClaimIndex<'a> {
+    pub fn new(claims : &'a [Claim]) -> ClaimIndex<'a> {
+        let mut index = ClaimIndex { claims, nodes: Vec::new(), root: None };
+        let mut level : Vec<(Rect, usize)> = claims.iter().enumerate().map(|(i, c)| (c.rect.clone(), i)).collect();
+        let mut leaf = true;
+        while !level.is_empty() {
+            level = index.pack(level, leaf);
+            leaf = false;
+            if level.len() == 1 {
+                index.root = Some(level[0].1);
+                break;
+            }
+        }
+        index
+    }
+
+    // Groups entries into nodes of up to CAPACITY, returning the new nodes' bounds and indexes.
+    fn pack(&mut self, mut entries : Vec<(Rect, usize)>, leaf : bool) -> Vec<(Rect, usize)> {
+        let node_count = entries.len().div_ceil(CAPACITY);
+        let slab_size = (node_count as f64).sqrt().ceil() as usize * CAPACITY;
+        entries.sort_by_key(|(r, _)| i64::from(r.left) + r.right());
+
+        let mut packed = Vec::with_capacity(node_count);
+        for slab in entries.chunks_mut(slab_size) {
+            slab.sort_by_key(|(r, _)| i64::from(r.top) + r.bottom());
+            for group in slab.chunks(CAPACITY) {
+                let bounds = group.iter().fold(group[0].0.clone(), |bounds, (r, _)| bounds.union_bounds(r));
+                self.nodes.push(Node { bounds: bounds.clone(), entries: group.iter().map(|(_, i)| *i).collect(), leaf });
+                packed.push((bounds, self.nodes.len() - 1));
+            }
+        }
+        packed
+    }
+
+    // The indexes of every claim that intersects `rect`, in order.
+    pub fn intersecting(&self, rect : &Rect) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut pending : Vec<usize> = self.root.into_iter().collect();
+        while let Some(n) = pending.pop() {
+            let node = &self.nodes[n];
+            if !node.bounds.intersects_with(rect) {
+                continue;
+            }
+            if node.leaf {
+                found.extend(node.entries.iter().filter(|i| self.claims[**i].rect.intersects_with(rect)));
+            } else {
+                pending.extend(node.entries.iter());
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+
+    // For each claim, the indexes of the other claims it intersects.
+    pub fn overlap_graph(&self) -> Vec<Vec<usize>> {
+        self.claims
+            .iter()
+            .enumerate()
+            .map(|(i, claim)| self.intersecting(&claim.rect).into_iter().filter(|j| *j != i).collect())
+            .collect()
+    }
+
+    // The claims that intersect no other claim, in order.
+    pub fn isolated(&self) -> Vec<&'a Claim> {
+        self.claims
+            .iter()
+            .enumerate()
+            .filter(|(i, claim)| self.intersecting(&claim.rect) == [*i])
+            .map(|(_, claim)| claim)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(count : i32) -> Vec<Claim> {
+        // A scattering of claims, dense enough that some overlap and some don't.
+        (0..count).map(|i| Claim::new(format!("#{}", i + 1), (i * 37) % 101, (i * 53) % 97, 1 + i % 7, 1 + i % 5)).collect()
+    }
+
+    #[test]
+    fn queries_match_checking_every_claim() {
+        let claims = claims(200);
+        let index = ClaimIndex::new(&claims);
+        for rect in [Rect::new(0, 0, 10, 10), Rect::new(50, 40, 3, 30), Rect::new(-10, -10, 5, 5), Rect::new(0, 0, 200, 200)] {
+            let expected : Vec<usize> = (0..claims.len()).filter(|i| claims[*i].rect.intersects_with(&rect)).collect();
+            assert_eq!(index.intersecting(&rect), expected);
+        }
+    }
+
+    #[test]
+    fn the_overlap_graph_lists_each_claims_neighbours() {
+        let claims = claims(150);
+        let graph = ClaimIndex::new(&claims).overlap_graph();
+        for (i, neighbours) in graph.iter().enumerate() {
+            let expected : Vec<usize> = (0..claims.len()).filter(|j| *j != i && claims[*j].rect.intersects_with(&claims[i].rect)).collect();
+            assert_eq!(*neighbours, expected);
+        }
+    }
+
+    #[test]
+    fn claims_at_the_edge_of_i32_are_indexed() {
+        let claims = vec!(
+            Claim::new("#1".to_string(), 2147483000, 0, 1000, 1),
+            Claim::new("#2".to_string(), 2147483600, 0, 2147483647, 1),
+            Claim::new("#3".to_string(), -2147483648, 0, 2147483647, 1),
+            Claim::new("#4".to_string(), i32::MAX, i32::MAX, i32::MAX, i32::MAX));
+        let index = ClaimIndex::new(&claims);
+        assert_eq!(index.overlap_graph(), vec!(vec!(1), vec!(0), vec!(), vec!()));
+        let ids : Vec<&str> = index.isolated().iter().map(|c| c.id()).collect();
+        assert_eq!(ids, vec!("#3", "#4"));
+        assert_eq!(index.intersecting(&Rect::new(i32::MAX, 0, 1, 1)), vec!(0, 1));
+    }
+
+    #[test]
+    fn isolated_claims_are_found() {
+        let claims = vec!(
+            Claim::new("#1".to_string(), 1, 3, 4, 4),
+            Claim::new("#2".to_string(), 3, 1, 4, 4),
+            Claim::new("#3".to_string(), 8, 8, 2, 2),
+            Claim::new("#4".to_string(), 20, 20, 1, 1));
+        let ids : Vec<&str> = ClaimIndex::new(&claims).isolated().iter().map(|c| c.id()).collect();
+        assert_eq!(ids, vec!("#3", "#4"));
+        assert!(ClaimIndex::new(&[]).isolated().is_empty());
+    }
+}
@@ -0,0 +1,561 @@
+pub mod analysis;
+pub mod heatmap;
+pub mod index;
+pub mod sweep;
+
+use aoc_common::{ParseError, ParseErrors, Puzzle};
+use std::fmt;
+
+pub struct Day3;
+
+impl Puzzle for Day3 {
+    const DAY : u32 = 3;
+
+    type Input = Vec<Claim>;
+    type Part1 = u128;
+    type Part2 = Option<String>;
+
+    fn parse(input : &str) -> Result<Vec<Claim>, ParseErrors> {
+        aoc_common::parse_lines(input, Claim::parse)
+    }
+
+    fn part1(claims : &Vec<Claim>) -> u128 {
+        let rects : Vec<Rect> = claims.iter().map(|c| c.rect.clone()).collect();
+        sweep::overlap_area(&rects)
+    }
+
+    fn part2(claims : &Vec<Claim>) -> Option<String> {
+        isolated_claim(claims).map(|claim| claim.id().to_string())
+    }
+}
+
+// A half-open rectangle: it covers left <= x < right and top <= y < bottom, so
+// rects that only touch along an edge don't intersect, and a rect with no width
+// or height covers nothing at all. Claims are any i32 position and size, so
+// edges, sizes and areas are worked out in wider types: a claim can reach past
+// i32::MAX, and the bounds of several claims can be wider than any one of them.
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(PartialEq)]
+pub struct Rect {
+    left : i32,
+    top : i32,
+    width : i64,
+    height : i64
+}
+
+impl Rect {
+    pub fn new(left : i32, top : i32, width : i32, height : i32) -> Rect {
+        Rect { left, top, width: i64::from(width), height: i64::from(height) }
+    }
+
+    // Left and top are always those of a claim, so they fit in an i32.
+    fn from_edges(left : i32, top : i32, right : i64, bottom : i64) -> Rect {
+        Rect { left, top, width: right - i64::from(left), height: bottom - i64::from(top) }
+    }
+
+    pub fn left(&self) -> i32 {
+        self.left
+    }
+
+    pub fn top(&self) -> i32 {
+        self.top
+    }
+
+    pub fn right(&self) -> i64 {
+        i64::from(self.left) + self.width
+    }
+
+    pub fn bottom(&self) -> i64 {
+        i64::from(self.top) + self.height
+    }
+
+    pub fn width(&self) -> i64 {
+        self.width
+    }
+
+    pub fn height(&self) -> i64 {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    pub fn area(&self) -> u128 {
+        if self.is_empty() { 0 } else { self.width as u128 * self.height as u128 }
+    }
+
+    pub fn contains_point(&self, x : i32, y : i32) -> bool {
+        self.left <= x && i64::from(x) < self.right() && self.top <= y && i64::from(y) < self.bottom()
+    }
+
+    pub fn intersects_with(&self, other : &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    // The area both rects cover, if there is any.
+    pub fn intersection(&self, other : &Rect) -> Option<Rect> {
+        let left = self.left.max(other.left);
+        let top = self.top.max(other.top);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        let rect = Rect::from_edges(left, top, right, bottom);
+        if self.is_empty() || other.is_empty() || rect.is_empty() { None } else { Some(rect) }
+    }
+
+    // The smallest rect covering both. Empty rects cover nothing, so they're ignored.
+    pub fn union_bounds(&self, other : &Rect) -> Rect {
+        if other.is_empty() {
+            return self.clone();
+        }
+        if self.is_empty() {
+            return other.clone();
+        }
+        let left = self.left.min(other.left);
+        let top = self.top.min(other.top);
+        Rect::from_edges(left, top, self.right().max(other.right()), self.bottom().max(other.bottom()))
+    }
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Claim {
+    id : String,
+    rect : Rect
+}
+
+impl Claim {
+    pub fn new(id : String, left : i32, top : i32, width : i32, height : i32) -> Claim {
+        Claim { id, rect: Rect::new(left, top, width, height) }
+    }
+
+    // Parses exactly `#id @ x,y: wxh`, as written by Display.
+    pub fn parse(s : &str) -> Result<Claim, ParseError> {
+        Claim::parse_with(s, Format::Strict)
+    }
+
+    pub fn parse_with(s : &str, format : Format) -> Result<Claim, ParseError> {
+        let (at, colon) = match format {
+            Format::Strict => (" @ ", ": "),
+            Format::Relaxed => ("@", ":"),
+        };
+        let error = || ParseError::new(s, "a claim like '#1 @ 1,3: 4x4'");
+        let (id, rest) = s.split_once(at).ok_or_else(error)?;
+        let (position, size) = rest.split_once(colon).ok_or_else(error)?;
+
+        let id = format.trim(id);
+        let number = id.strip_prefix('#').map(|n| format.trim(n));
+        if !number.is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())) {
+            return Err(ParseError::new(id, "an id like '#1'"));
+        }
+        let (left, top) = parse_pair(position, ',', true, format, "a position like '1,3'")?;
+        let (width, height) = parse_pair(size, 'x', false, format, "a size like '4x4'")?;
+        Ok(Claim::new(format!("#{}", number.unwrap_or_default()), left, top, width, height))
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn rect(&self) -> &Rect {
+        &self.rect
+    }
+}
+
+// How strictly Claim::parse_with sticks to the `#1 @ 1,3: 4x4` layout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    // Single spaces around '@' and after ':', and nowhere else.
+    Strict,
+    // Any whitespace, or none, around '#', '@', ',', ':' and 'x', but never
+    // inside a number.
+    Relaxed,
+}
+
+impl Format {
+    fn trim(self, s : &str) -> &str {
+        match self {
+            Format::Strict => s,
+            Format::Relaxed => s.trim(),
+        }
+    }
+}
+
+impl fmt::Display for Claim {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} @ {},{}: {}x{}", self.id, self.rect.left, self.rect.top, self.rect.width, self.rect.height)
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum FabricState {
+    Empty,
+    Single,
+    Multiple
+}
+
+impl FabricState {
+    pub fn from_count(count : u32) -> FabricState {
+        match count {
+            0 => FabricState::Empty,
+            1 => FabricState::Single,
+            _ => FabricState::Multiple,
+        }
+    }
+}
+
+// A grid of square inches, each holding how many claims cover it.
+#[derive(Debug)]
+pub struct Fabric {
+    width : usize,
+    height : usize,
+    inches : Vec<Vec<u32>>
+}
+
+impl Fabric {
+    pub fn new(width : usize, height : usize) -> Self {
+        Fabric { width, height, inches: vec!(vec!(0; width); height) }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // How many claims cover the inch at x, y. Inches off the fabric have none.
+    pub fn count(&self, x : i32, y : i32) -> u32 {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return 0;
+        }
+        self.inches[y as usize][x as usize]
+    }
+
+    pub fn state(&self, x : i32, y : i32) -> FabricState {
+        FabricState::from_count(self.count(x, y))
+    }
+
+    pub fn max_count(&self) -> u32 {
+        self.inches.iter().flat_map(|row| row.iter()).copied().max().unwrap_or(0)
+    }
+
+    pub fn fill(&mut self, claim : &Claim) {
+        let x1 = claim.rect.left().max(0);
+        let y1 = claim.rect.top().max(0);
+        let x2 = claim.rect.right().min(self.width as i64);
+        let y2 = claim.rect.bottom().min(self.height as i64);
+        for y in i64::from(y1)..y2 {
+            for x in i64::from(x1)..x2 {
+                self.plot(x as usize, y as usize);
+            }
+        }
+    }
+
+    pub fn plot(&mut self, x : usize, y : usize) {
+        self.inches[y][x] += 1;
+    }
+
+    pub fn count_multiples(&self) -> i32 {
+        self.inches.iter().map(|row| row.iter().filter(|count| **count > 1).count() as i32).sum()
+    }
+
+    pub fn count_singles(&self) -> i32 {
+        self.inches.iter().map(|row| row.iter().filter(|count| **count == 1).count() as i32).sum()
+    }
+
+    pub fn debug(&self) {
+        println!("Fabric {}x{}", self.width, self.height);
+        for row in self.inches.iter() {
+            for cell in row.iter() {
+                let c = match FabricState::from_count(*cell) {
+                    FabricState::Empty => ' ',
+                    FabricState::Single => '.',
+                    FabricState::Multiple => 'X',
+                };
+                print!("{}", c);
+            }
+            println!();
+        }
+    }
+}
+
+pub fn isolated_claim(claims : &[Claim]) -> Option<&Claim> {
+    index::ClaimIndex::new(claims).isolated().first().copied()
+}
+
+pub fn line_to_claim(s : &str) -> Result<(String, i32, i32, i32, i32), ParseError> {
+    let claim = Claim::parse(s)?;
+    let rect = claim.rect;
+    // A parsed claim's size is the i32 it was written as.
+    Ok((claim.id, rect.left, rect.top, rect.width as i32, rect.height as i32))
+}
+
+// Two plain integers with `separator` between them. Only positions may be negative.
+fn parse_pair(s : &str, separator : char, signed : bool, format : Format, expected : &str) -> Result<(i32, i32), ParseError> {
+    let number = |n : &str| {
+        let n = format.trim(n);
+        let digits = if signed { n.strip_prefix('-').unwrap_or(n) } else { n };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        n.parse::<i32>().ok()
+    };
+    s.split_once(separator)
+        .and_then(|(a, b)| Some((number(a)?, number(b)?)))
+        .ok_or_else(|| ParseError::new(s, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Lcg;
+
+    #[test]
+    fn it_works() {
+        assert_eq!(2 + 2, 4);
+    }
+
+    #[test]
+    fn an_empty_fabric_has_a_count_of_zero() {
+        let fabric = Fabric::new(10, 10);
+        assert_eq!(0, fabric.count_multiples());
+    }
+
+    #[test]
+    fn a_fabric_with_only_1_rect_has_a_count_of_zero() {
+        let mut fabric = Fabric::new(10, 10);
+        fabric.fill(&Claim::new("one".to_string(), 1, 1, 5, 5));
+        assert_eq!(0, fabric.count_multiples());
+    }
+
+    #[test]
+    fn a_fabric_with_the_same_rect_twice_has_a_count_of_the_area() {
+        let mut fabric = Fabric::new(10, 10);
+        fabric.fill(&Claim::new("one".to_string(), 1, 1, 5, 5));
+        fabric.fill(&Claim::new("one".to_string(), 1, 1, 5, 5));
+        assert_eq!(25, fabric.count_multiples());
+    }
+
+    #[test]
+    fn a_fabric_with_the_same_rect_thrice_has_a_count_of_the_area() {
+        let mut fabric = Fabric::new(10, 10);
+        fabric.fill(&Claim::new("one".to_string(), 1, 1, 5, 5));
+        fabric.fill(&Claim::new("one".to_string(), 1, 1, 5, 5));
+        fabric.fill(&Claim::new("one".to_string(), 1, 1, 5, 5));
+        assert_eq!(25, fabric.count_multiples());
+    }
+
+
+    #[test]
+    fn a_fabric_with_two_non_overlapping_rects_has_count_zero() {
+        let mut fabric = Fabric::new(10, 10);
+        fabric.fill(&Claim::new("one".to_string(), 0, 0, 2, 2));
+        fabric.fill(&Claim::new("two".to_string(), 2, 2, 3, 3));
+        assert_eq!(0, fabric.count_multiples());
+    }
+
+    #[test]
+    fn a_fabric_with_two_overlapping_rects_counts_the_overlap() {
+        let mut fabric = Fabric::new(10, 10);
+        fabric.fill(&Claim::new("one".to_string(), 0, 0, 2, 2));
+        fabric.fill(&Claim::new("two".to_string(), 1, 1, 3, 3));
+        // fabric.debug();
+        assert_eq!(1, fabric.count_multiples());
+    }
+
+    #[test]
+    fn example_from_aoc_works() {
+        let mut fabric = Fabric::new(8, 8);
+        fabric.fill(&Claim::new("one".to_string(), 1, 3, 4, 4));
+        fabric.fill(&Claim::new("two".to_string(), 3, 1, 4, 4));
+        fabric.fill(&Claim::new("three".to_string(), 5, 5, 2, 2));
+        // fabric.debug();
+        assert_eq!(4, fabric.count_multiples());
+    }
+
+    #[test]
+    fn example_claims_are_solved() {
+        let claims = Day3::parse("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n").unwrap();
+        assert_eq!(Day3::part1(&claims), 4);
+    }
+
+    #[test]
+    fn claims_past_a_1000_inch_fabric_still_count() {
+        let claims = Day3::parse("#1 @ 990,990: 20x20\n#2 @ 995,-5: 10x1010\n").unwrap();
+        assert_eq!(Day3::part1(&claims), 150);
+    }
+
+    #[test]
+    fn the_isolated_claim_is_found() {
+        let claims = Day3::parse("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 8,8: 2x2\n").unwrap();
+        assert_eq!(Day3::part2(&claims), Some("#3".to_string()));
+    }
+
+    #[test]
+    fn claims_past_i32_max_are_solved() {
+        let claims = Day3::parse("#1 @ 2147483000,0: 1000x1\n#2 @ 0,0: 70000x70000\n").unwrap();
+        assert_eq!(Day3::part1(&claims), 0);
+        assert_eq!(Day3::part2(&claims), Some("#1".to_string()));
+        assert_eq!(claims[0].rect().right(), 2147484000);
+        assert_eq!(claims[1].rect().area(), 4_900_000_000);
+    }
+
+    #[test]
+    fn claims_that_all_overlap_have_no_answer() {
+        let claims = Day3::parse("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n").unwrap();
+        assert_eq!(Day3::part2(&claims), None);
+    }
+
+    #[test]
+    fn the_example_isolated_claim_is_found() {
+        let claims = Day3::parse("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n").unwrap();
+        assert_eq!(Day3::part2(&claims), Some("#3".to_string()));
+    }
+
+    #[test]
+    fn rects_are_half_open() {
+        let rect = Rect::new(1, 1, 2, 2);
+        assert!(rect.contains_point(1, 1));
+        assert!(rect.contains_point(2, 2));
+        assert!(!rect.contains_point(3, 2));
+        assert!(!rect.contains_point(2, 3));
+        assert!(!rect.intersects_with(&Rect::new(3, 1, 2, 2)));
+        assert!(!rect.intersects_with(&Rect::new(1, 3, 2, 2)));
+        assert!(rect.intersects_with(&Rect::new(2, 2, 2, 2)));
+    }
+
+    #[test]
+    fn empty_rects_cover_nothing() {
+        let empty = Rect::new(2, 2, 0, 3);
+        assert!(empty.is_empty());
+        assert!(Rect::new(2, 2, 3, -1).is_empty());
+        assert_eq!(empty.area(), 0);
+        assert_eq!(Rect::new(2, 2, -3, -1).area(), 0);
+        assert!(!empty.contains_point(2, 2));
+        assert!(!empty.intersects_with(&Rect::new(0, 0, 10, 10)));
+        assert_eq!(empty.union_bounds(&Rect::new(5, 5, 1, 1)), Rect::new(5, 5, 1, 1));
+    }
+
+    #[test]
+    fn intersections_and_bounds() {
+        let a = Rect::new(0, 0, 4, 3);
+        let b = Rect::new(2, 1, 5, 5);
+        assert_eq!(a.intersection(&b), Some(Rect::new(2, 1, 2, 2)));
+        assert_eq!(b.intersection(&a), Some(Rect::new(2, 1, 2, 2)));
+        assert_eq!(a.intersection(&Rect::new(4, 0, 1, 1)), None);
+        assert_eq!(a.union_bounds(&b), Rect::new(0, 0, 7, 6));
+    }
+
+    #[test]
+    fn fill_reaches_the_last_row_and_column() {
+        let mut fabric = Fabric::new(4, 4);
+        fabric.fill(&Claim::new("one".to_string(), 2, 2, 5, 5));
+        fabric.fill(&Claim::new("two".to_string(), 3, 3, 1, 1));
+        assert_eq!(fabric.count_singles(), 3);
+        assert_eq!(fabric.count_multiples(), 1);
+    }
+
+    // A small rect for the property tests below.
+    fn rect(rng : &mut Lcg) -> Rect {
+        let mut next = |bound| rng.below(bound) as i32;
+        Rect::new(next(20), next(20), next(8), next(8))
+    }
+
+    fn cells(rect : &Rect) -> Vec<(i32, i32)> {
+        (0..30).flat_map(|y| (0..30).map(move |x| (x, y))).filter(|(x, y)| rect.contains_point(*x, *y)).collect()
+    }
+
+    #[test]
+    fn geometry_agrees_with_the_cells_covered() {
+        let mut rng = Lcg::new(3);
+        for _ in 0..500 {
+            let (a, b) = (rect(&mut rng), rect(&mut rng));
+            let shared : Vec<_> = cells(&a).into_iter().filter(|(x, y)| b.contains_point(*x, *y)).collect();
+            assert_eq!(a.area() as usize, cells(&a).len(), "{:?}", a);
+            assert_eq!(a.intersects_with(&b), !shared.is_empty(), "{:?} {:?}", a, b);
+            assert_eq!(a.intersection(&b).map_or(0, |i| i.area()) as usize, shared.len(), "{:?} {:?}", a, b);
+            let bounds = a.union_bounds(&b);
+            assert!(cells(&a).iter().chain(cells(&b).iter()).all(|(x, y)| bounds.contains_point(*x, *y)));
+        }
+    }
+
+    #[test]
+    fn the_grid_and_the_geometry_agree() {
+        let mut rng = Lcg::new(17);
+        for round in 0..50 {
+            let claims : Vec<Claim> = (0..10).map(|i| {
+                let r = rect(&mut rng);
+                Claim::new(format!("#{}", i), r.left(), r.top(), r.width() as i32, r.height() as i32)
+            }).collect();
+
+            let mut fabric = Fabric::new(30, 30);
+            for claim in claims.iter() {
+                fabric.fill(claim);
+            }
+            let rects : Vec<Rect> = claims.iter().map(|c| c.rect().clone()).collect();
+            assert_eq!(fabric.count_multiples() as u128, sweep::overlap_area(&rects), "round {}", round);
+            assert_eq!((fabric.count_singles() + fabric.count_multiples()) as u128, sweep::coverage(&rects).covered, "round {}", round);
+
+            let isolated : Vec<&str> = index::ClaimIndex::new(&claims).isolated().iter().map(|c| c.id()).collect();
+            let alone : Vec<&str> = claims.iter()
+                .filter(|c| !c.rect().is_empty() && cells(c.rect()).iter().all(|(x, y)| claims.iter().filter(|o| o.rect().contains_point(*x, *y)).count() == 1))
+                .map(|c| c.id())
+                .collect();
+            assert_eq!(isolated, alone, "round {}", round);
+        }
+    }
+
+    #[test]
+    fn malformed_claims_are_errors() {
+        assert_eq!(Claim::parse("#1 @ 1,3 4x4").err(), Some(ParseError::new("#1 @ 1,3 4x4", "a claim like '#1 @ 1,3: 4x4'")));
+        assert_eq!(Claim::parse("#1 @ 1,a: 4x4").err(), Some(ParseError::new("1,a", "a position like '1,3'")));
+        assert_eq!(Claim::parse("#1 @ 1,3: 4x").err(), Some(ParseError::new("4x", "a size like '4x4'")));
+        assert_eq!(Claim::parse("#1 @ 1,3: 4x4x4").err(), Some(ParseError::new("4x4x4", "a size like '4x4'")));
+    }
+
+    #[test]
+    fn claims_are_checked_against_the_grammar() {
+        assert_eq!(Claim::parse("1 @ 1,3: 4x4").err(), Some(ParseError::new("1", "an id like '#1'")));
+        assert_eq!(Claim::parse("#a @ 1,3: 4x4").err(), Some(ParseError::new("#a", "an id like '#1'")));
+        assert_eq!(Claim::parse("#1  @ 1,3: 4x4").err(), Some(ParseError::new("#1 ", "an id like '#1'")));
+        assert_eq!(Claim::parse("#1 @ +1,3: 4x4").err(), Some(ParseError::new("+1,3", "a position like '1,3'")));
+        assert_eq!(Claim::parse("#1 @ 1,3: -4x4").err(), Some(ParseError::new("-4x4", "a size like '4x4'")));
+        assert_eq!(Claim::parse("#1 @ 1,3: 99999999999x4").err(), Some(ParseError::new("99999999999x4", "a size like '4x4'")));
+        assert_eq!(Claim::parse("").err(), Some(ParseError::new("", "a claim like '#1 @ 1,3: 4x4'")));
+        assert_eq!(Claim::parse("#1 @ -1,-3: 4x4").unwrap().rect(), &Rect::new(-1, -3, 4, 4));
+    }
+
+    #[test]
+    fn claims_round_trip_through_display() {
+        for line in ["#1 @ 1,3: 4x4", "#1234 @ 0,999: 1x27", "#7 @ -5,-10: 0x0"] {
+            let claim = Claim::parse(line).unwrap();
+            assert_eq!(claim.to_string(), line);
+            assert_eq!(Claim::parse(&claim.to_string()).unwrap().rect(), claim.rect());
+        }
+    }
+
+    #[test]
+    fn relaxed_claims_allow_any_whitespace() {
+        for line in ["#1 @ 1,3: 4x4", "#1@1,3:4x4", "  #1   @\t1 , 3 :4 x 4 "] {
+            assert_eq!(Claim::parse_with(line, Format::Relaxed).unwrap().to_string(), "#1 @ 1,3: 4x4");
+        }
+        assert!(Claim::parse("#1@1,3:4x4").is_err());
+        assert!(Claim::parse_with("# 1 @ 1,3 4x4", Format::Relaxed).is_err());
+        assert!(Claim::parse_with("#1 @ 1 3: 4x4", Format::Relaxed).is_err());
+        assert_eq!(Claim::parse_with("#1 @ 1 2,3: 4x4", Format::Relaxed).err(), Some(ParseError::new(" 1 2,3", "a position like '1,3'")));
+        assert!(Claim::parse_with("#1 @ 1,3: 4 0x4", Format::Relaxed).is_err());
+        assert!(Claim::parse_with("#1 2 @ 1,3: 4x4", Format::Relaxed).is_err());
+        assert!(Claim::parse_with("#1 @ - 1,3: 4x4", Format::Relaxed).is_err());
+    }
+
+    #[test]
+    fn every_malformed_claim_is_reported() {
+        let errors = Day3::parse("#1 @ 1,3: 4x4\nnonsense\n#3 @ 5,5: 2x2\n#4 @ 5,5:2x2").unwrap_err();
+        let lines : Vec<_> = errors.0.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec!(Some(2), Some(4)));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::Rect;
+
+// Areas found by sweeping a vertical line across the rects from left to right.
+// The line's y coordinates are compressed down to the rects' own edges, so the
+// cost depends only on how many rects there are, never on how big they are or
+// where they sit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coverage {
+    // Area under at least one rect.
+    pub covered : u128,
+    // Area under two or more rects.
+    pub overlapped : u128,
+}
+
+pub fn coverage(rects : &[Rect]) -> Coverage {
+    let rects : Vec<_> = rects.iter().filter(|r| r.width > 0 && r.height > 0).collect();
+
+    let mut ys : Vec<i64> = rects.iter().flat_map(|r| [i64::from(r.top), r.bottom()]).collect();
+    ys.sort_unstable();
+    ys.dedup();
+
+    // (x, +1 or -1, top, bottom), with the rect's edges as indices into ys.
+    let mut events : Vec<(i64, i32, usize, usize)> = Vec::with_capacity(rects.len() * 2);
+    for r in rects.iter() {
+        let top = ys.binary_search(&i64::from(r.top)).expect("every top is in ys");
+        let bottom = ys.binary_search(&r.bottom()).expect("every bottom is in ys");
+        events.push((i64::from(r.left), 1, top, bottom));
+        events.push((r.right(), -1, top, bottom));
+    }
+    events.sort_unstable();
+
+    let mut tree = CoverTree::new(&ys);
+    let mut area = Coverage { covered: 0, overlapped: 0 };
+    let mut last_x = events.first().map_or(0, |e| e.0);
+    for (x, delta, top, bottom) in events {
+        let width = (x - last_x) as u128;
+        area.covered += width * tree.covered() as u128;
+        area.overlapped += width * tree.overlapped() as u128;
+        tree.add(top, bottom, delta);
+        last_x = x;
+    }
+    area
+}
+
+pub fn overlap_area(rects : &[Rect]) -> u128 {
+    coverage(rects).overlapped
+}
+
+// A segment tree over the gaps between consecutive ys, tracking how much of
+// the sweep line is covered once and how much twice or more.
+struct CoverTree<'a> {
+    ys : &'a [i64],
+    // How many rects cover the whole of each node's span without covering its parent's.
+    counts : Vec<i32>,
+    once : Vec<i64>,
+    twice : Vec<i64>,
+}
+
+impl<'a> CoverTree<'a> {
+    fn new(ys : &'a [i64]) -> CoverTree<'a> {
+        let nodes = 4 * ys.len().max(1);
+        CoverTree { ys, counts: vec!(0; nodes), once: vec!(0; nodes), twice: vec!(0; nodes) }
+    }
+
+    fn covered(&self) -> i64 {
+        self.once[1]
+    }
+
+    fn overlapped(&self) -> i64 {
+        self.twice[1]
+    }
+
+    fn add(&mut self, from : usize, to : usize, delta : i32) {
+        if self.ys.len() > 1 {
+            self.update(1, 0, self.ys.len() - 1, from, to, delta);
+        }
+    }
+
+    // Node `node` spans ys[lo]..ys[hi]; the change applies to ys[from]..ys[to].
+    fn update(&mut self, node : usize, lo : usize, hi : usize, from : usize, to : usize, delta : i32) {
+        if to <= lo || hi <= from {
+            return;
+        }
+        if from <= lo && hi <= to {
+            self.counts[node] += delta;
+        } else {
+            let mid = (lo + hi) / 2;
+            self.update(node * 2, lo, mid, from, to, delta);
+            self.update(node * 2 + 1, mid, hi, from, to, delta);
+        }
+        self.pull(node, lo, hi);
+    }
+
+    fn pull(&mut self, node : usize, lo : usize, hi : usize) {
+        let full = self.ys[hi] - self.ys[lo];
+        let leaf = hi - lo == 1;
+        let (children_once, children_twice) = if leaf {
+            (0, 0)
+        } else {
+            (self.once[node * 2] + self.once[node * 2 + 1], self.twice[node * 2] + self.twice[node * 2 + 1])
+        };
+        match self.counts[node] {
+            0 => {
+                self.once[node] = children_once;
+                self.twice[node] = children_twice;
+            },
+            1 => {
+                self.once[node] = full;
+                self.twice[node] = children_once;
+            },
+            _ => {
+                self.once[node] = full;
+                self.twice[node] = full;
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_example_overlaps_by_four() {
+        let rects = [Rect::new(1, 3, 4, 4), Rect::new(3, 1, 4, 4), Rect::new(5, 5, 2, 2)];
+        assert_eq!(coverage(&rects), Coverage { covered: 32, overlapped: 4 });
+    }
+
+    #[test]
+    fn triple_overlaps_count_once() {
+        let rect = Rect::new(1, 1, 5, 5);
+        assert_eq!(coverage(&[rect.clone(), rect.clone(), rect]), Coverage { covered: 25, overlapped: 25 });
+    }
+
+    #[test]
+    fn touching_rects_do_not_overlap() {
+        assert_eq!(overlap_area(&[Rect::new(0, 0, 2, 2), Rect::new(2, 0, 2, 2), Rect::new(0, 2, 4, 1)]), 0);
+        assert_eq!(overlap_area(&[]), 0);
+        assert_eq!(overlap_area(&[Rect::new(0, 0, 0, 5), Rect::new(0, 0, 5, 5)]), 0);
+    }
+
+    #[test]
+    fn negative_and_huge_coordinates_are_exact() {
+        let rects = [Rect::new(-5, -5, 10, 10), Rect::new(0, 0, 10, 10)];
+        assert_eq!(coverage(&rects), Coverage { covered: 175, overlapped: 25 });
+
+        let side = i32::MAX as u128;
+        let low = Rect::new(i32::MIN, i32::MIN, i32::MAX, i32::MAX);
+        assert_eq!(coverage(&[low.clone(), low]), Coverage { covered: side * side, overlapped: side * side });
+
+        // Its right and bottom edges are well past i32::MAX.
+        let high = Rect::new(i32::MAX - 1, i32::MAX - 1, i32::MAX, i32::MAX);
+        let corner = Rect::new(i32::MAX - 1, i32::MAX - 1, 2, 2);
+        assert_eq!(coverage(&[high, corner]), Coverage { covered: side * side, overlapped: 4 });
+    }
+}
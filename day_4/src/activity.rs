@@ -1,20 +1,26 @@
+use crate::minutes::Minutes;
+use crate::window::Window;
 use chrono::prelude::*;
 use chrono::Duration;
-use std::collections::HashMap;
 
 #[derive(Debug)]
 #[derive(Clone)]
 pub struct Activity {
   started_at : NaiveDateTime,
-  activity : HashMap<u32, bool>
+  window : Window,
+  activity : Minutes
 }
 
 impl Activity {
   pub fn new(dt : NaiveDateTime) -> Activity {
-    let a = HashMap::new();
+    Activity::with_window(dt, Window::default())
+  }
+
+  pub fn with_window(dt : NaiveDateTime, window : Window) -> Activity {
     Activity { 
       started_at: dt,
-      activity:  a
+      window,
+      activity: Minutes::new()
     }
   }
 
@@ -22,21 +28,27 @@ impl Activity {
     let first = self.clamp_start(start);
     let last = self.clamp_stop(stop);
 
-    println!("Between {} and {}", first, last);
-
     let one_minute = Duration::minutes(1);
     let mut now = first;
     while now < last {
-      self.activity.insert(now.minute(), true);
-      // self.activity[now.minute()] = '#';
+      self.activity.insert((now - self.window_start()).num_minutes() as u32);
       now = now.checked_add_signed(one_minute).unwrap();
     }
   }
 
+  // The minutes into the window that the guard was asleep.
+  pub fn asleep(&self) -> &Minutes {
+    &self.activity
+  }
+
+  pub fn window(&self) -> Window {
+    self.window
+  }
+
   pub fn chart(&self) -> String {
-    let mut result = String::with_capacity(60);
-    for m in 0..60 {
-      if *self.activity.get(&m).unwrap_or(&false) {
+    let mut result = String::with_capacity(self.window.minutes() as usize);
+    for m in 0..self.window.minutes() {
+      if self.activity.contains(m) {
         result.push('#');
       }
       else {
@@ -47,33 +59,23 @@ impl Activity {
   }
 
   pub fn shift_date(&self) -> NaiveDate {
-    if self.is_before_shift(&self.started_at) {
-      self.started_at.date().succ()
-    } else {
-      self.started_at.date()
-    }
+    self.window.date_for(&self.started_at)
+  }
+
+  pub fn window_start(&self) -> NaiveDateTime {
+    self.window.on(self.shift_date()).0
   }
 
-  fn is_before_shift(&self, date_time : &NaiveDateTime) -> bool {
-    date_time.hour() != 0
+  pub fn window_end(&self) -> NaiveDateTime {
+    self.window.on(self.shift_date()).1
   }
 
   fn clamp_start(&self, start : &NaiveDateTime) -> NaiveDateTime {
-    if start < &self.shift_date().and_hms(0, 0, 0) {
-      self.shift_date().and_hms(0, 0, 0)
-    }
-    else {
-      *start
-    }
+    (*start).max(self.window_start())
   }
 
   fn clamp_stop(&self, stop : &NaiveDateTime) -> NaiveDateTime {
-    if stop >= &self.shift_date().and_hms(1, 0, 0) {
-      self.shift_date().and_hms(1, 0, 0)
-    }
-    else {
-      *stop
-    }
+    (*stop).min(self.window_end())
   }
 }
 
@@ -83,41 +85,61 @@ mod tests {
 
     #[test]
     fn a_blank_activity_is_awake_for_the_whole_shift() {
-      let activity = Activity::new(NaiveDate::from_ymd(2018, 11, 1).and_hms(23, 25, 0));
+      let activity = Activity::new(NaiveDate::from_ymd_opt(2018, 11, 1).unwrap().and_hms_opt(23, 25, 0).unwrap());
 
       assert_eq!(activity.chart(), "............................................................".to_string());
     }
 
     #[test]
     fn the_date_of_a_shift_reflects_the_next_or_current_midnight_period() {
-      let activity = Activity::new(NaiveDate::from_ymd(2018, 11, 1).and_hms(23, 56, 0));
+      let activity = Activity::new(NaiveDate::from_ymd_opt(2018, 11, 1).unwrap().and_hms_opt(23, 56, 0).unwrap());
 
-      assert_eq!(activity.shift_date(), NaiveDate::from_ymd(2018, 11, 2));
+      assert_eq!(activity.shift_date(), NaiveDate::from_ymd_opt(2018, 11, 2).unwrap());
 
-      let activity = Activity::new(NaiveDate::from_ymd(2018, 11, 1).and_hms(0, 6, 0));
-      assert_eq!(activity.shift_date(), NaiveDate::from_ymd(2018, 11, 1));
+      let activity = Activity::new(NaiveDate::from_ymd_opt(2018, 11, 1).unwrap().and_hms_opt(0, 6, 0).unwrap());
+      assert_eq!(activity.shift_date(), NaiveDate::from_ymd_opt(2018, 11, 1).unwrap());
     }
 
     #[test]
     fn sleep_is_recorded() {
-      let mut activity = Activity::new(NaiveDate::from_ymd(2018, 11, 1).and_hms(23, 56, 0));
+      let mut activity = Activity::new(NaiveDate::from_ymd_opt(2018, 11, 1).unwrap().and_hms_opt(23, 56, 0).unwrap());
       activity.record_sleep(
-        &NaiveDate::from_ymd(2018, 11, 2).and_hms(0, 12, 0),
-        &NaiveDate::from_ymd(2018, 11, 2).and_hms(0, 20, 0));
+        &NaiveDate::from_ymd_opt(2018, 11, 2).unwrap().and_hms_opt(0, 12, 0).unwrap(),
+        &NaiveDate::from_ymd_opt(2018, 11, 2).unwrap().and_hms_opt(0, 20, 0).unwrap());
 
       assert_eq!(activity.chart(), "............########........................................".to_string());
     }
 
     #[test]
     fn out_of_bounds_sleeps_are_truncated() {
-      let mut activity = Activity::new(NaiveDate::from_ymd(2018, 11, 1).and_hms(23, 45, 0));
+      let mut activity = Activity::new(NaiveDate::from_ymd_opt(2018, 11, 1).unwrap().and_hms_opt(23, 45, 0).unwrap());
       activity.record_sleep(
-        &NaiveDate::from_ymd(2018, 11, 1).and_hms(23, 56, 0),
-        &NaiveDate::from_ymd(2018, 11, 2).and_hms(0, 6, 0));
+        &NaiveDate::from_ymd_opt(2018, 11, 1).unwrap().and_hms_opt(23, 56, 0).unwrap(),
+        &NaiveDate::from_ymd_opt(2018, 11, 2).unwrap().and_hms_opt(0, 6, 0).unwrap());
       activity.record_sleep(
-        &NaiveDate::from_ymd(2018, 11, 2).and_hms(0, 58, 0),
-        &NaiveDate::from_ymd(2018, 11, 2).and_hms(1, 3, 0));
+        &NaiveDate::from_ymd_opt(2018, 11, 2).unwrap().and_hms_opt(0, 58, 0).unwrap(),
+        &NaiveDate::from_ymd_opt(2018, 11, 2).unwrap().and_hms_opt(1, 3, 0).unwrap());
 
       assert_eq!(activity.chart(), "######....................................................##".to_string());
     }
+
+    #[test]
+    fn charts_cover_the_whole_window() {
+      let window = Window::new(NaiveTime::from_hms_opt(23, 0, 0).unwrap(), 120).unwrap();
+      let mut activity = Activity::with_window(NaiveDate::from_ymd_opt(2018, 11, 1).unwrap().and_hms_opt(22, 50, 0).unwrap(), window);
+      assert_eq!(activity.shift_date(), NaiveDate::from_ymd_opt(2018, 11, 1).unwrap());
+
+      activity.record_sleep(
+        &NaiveDate::from_ymd_opt(2018, 11, 1).unwrap().and_hms_opt(22, 55, 0).unwrap(),
+        &NaiveDate::from_ymd_opt(2018, 11, 1).unwrap().and_hms_opt(23, 2, 0).unwrap());
+      activity.record_sleep(
+        &NaiveDate::from_ymd_opt(2018, 11, 2).unwrap().and_hms_opt(0, 58, 0).unwrap(),
+        &NaiveDate::from_ymd_opt(2018, 11, 2).unwrap().and_hms_opt(1, 30, 0).unwrap());
+
+      let chart = activity.chart();
+      assert_eq!(chart.len(), 120);
+      assert_eq!(&chart[..3], "##.");
+      assert_eq!(&chart[117..], ".##");
+      assert_eq!(activity.asleep().count(), 4);
+    }
 }
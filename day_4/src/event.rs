@@ -1,3 +1,9 @@
+use aoc_common::ParseError;
+use crate::scan::Scanner;
+
+const GUARD : &str = "a guard like 'Guard #10 begins shift'";
+const EVENT : &str = "'Guard #N begins shift', 'falls asleep' or 'wakes up'";
+
 #[derive(Debug)]
 #[derive(PartialEq)]
 pub enum Event {
@@ -7,18 +13,53 @@ pub enum Event {
 }
 
 impl Event {
-    pub fn parse(s : &str) -> Event {
-        match s.chars().next().unwrap() {
-            'G' => {
-                let split : Vec<_> = s.split(' ').collect();
-                let guard_id_str : String = split[1].chars().skip(1).collect();
-                let guard_id : u32 = guard_id_str.parse().unwrap();
-                Event::StartShift(guard_id)
-            },
-            'f' => Event::FallAsleep,
-            'w' => Event::WakeUp,
-            _ => panic!("Unexpected event")
+    // The whole of `s` has to be one of the three events. Errors point at the
+    // column of `s` where it stopped matching.
+    pub fn parse(s : &str) -> Result<Event, ParseError> {
+        let mut scan = Scanner::new(s);
+        let event = if scan.eat("Guard") {
+            guard(&mut scan).map(Event::StartShift).ok_or(GUARD)
+        } else if scan.eat("falls asleep") {
+            Ok(Event::FallAsleep)
+        } else if scan.eat("wakes up") {
+            Ok(Event::WakeUp)
+        } else {
+            Err(EVENT)
+        };
+
+        match event {
+            Ok(event) if scan.is_done() => Ok(event),
+            Ok(_) => Err(ParseError::new(s, EVENT).at_column(scan.column())),
+            Err(expected) => Err(ParseError::new(s, expected).at_column(scan.column())),
         }
     }
 }
 
+// " #10 begins shift", after the "Guard".
+fn guard(scan : &mut Scanner) -> Option<u32> {
+    scan.eat(" #").then_some(())?;
+    let id = scan.number()?;
+    scan.eat(" begins shift").then_some(())?;
+    scan.is_done().then_some(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_event_is_parsed() {
+        assert_eq!(Event::parse("Guard #10 begins shift"), Ok(Event::StartShift(10)));
+        assert_eq!(Event::parse("falls asleep"), Ok(Event::FallAsleep));
+        assert_eq!(Event::parse("wakes up"), Ok(Event::WakeUp));
+    }
+
+    #[test]
+    fn the_whole_text_has_to_match() {
+        assert_eq!(Event::parse("foo"), Err(ParseError::new("foo", EVENT).at_column(1)));
+        assert_eq!(Event::parse("wibble"), Err(ParseError::new("wibble", EVENT).at_column(1)));
+        assert_eq!(Event::parse("falls asleep again"), Err(ParseError::new("falls asleep again", EVENT).at_column(13)));
+        assert_eq!(Event::parse("Guard #1 blah blah"), Err(ParseError::new("Guard #1 blah blah", GUARD).at_column(9)));
+        assert_eq!(Event::parse("Guard #10 begins shift!"), Err(ParseError::new("Guard #10 begins shift!", GUARD).at_column(23)));
+    }
+}
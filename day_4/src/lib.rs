@@ -0,0 +1,309 @@
+pub mod activity;
+pub mod event;
+pub mod minutes;
+pub mod report;
+pub mod shift;
+pub mod validate;
+pub mod window;
+mod scan;
+
+use aoc_common::{ParseError, ParseErrors, Puzzle};
+use chrono::prelude::*;
+use event::Event;
+use report::GuardReport;
+use scan::Scanner;
+use shift::Shift;
+
+pub struct Day4;
+
+impl Puzzle for Day4 {
+    const DAY : u32 = 4;
+
+    type Input = Vec<Shift>;
+    type Part1 = Option<u64>;
+    type Part2 = Option<u64>;
+
+    fn parse(input : &str) -> Result<Vec<Shift>, ParseErrors> {
+        let events = sort_events(aoc_common::parse_lines(input, parse_line)?);
+        Ok(Shift::from_events(&events))
+    }
+
+    fn part1(shifts : &Vec<Shift>) -> Option<u64> {
+        strategy_1(shifts)
+    }
+
+    fn part2(shifts : &Vec<Shift>) -> Option<u64> {
+        strategy_2(shifts)
+    }
+}
+
+// Guard id multiplied by the minute, for the guard who sleeps the most overall.
+// None if nobody ever sleeps.
+pub fn strategy_1(shifts : &[Shift]) -> Option<u64> {
+    GuardReport::new(shifts).strategy_1().map(|choice| choice.product)
+}
+
+// Guard id multiplied by the minute, for the guard most frequently asleep on the same minute.
+// None if nobody ever sleeps.
+pub fn strategy_2(shifts : &[Shift]) -> Option<u64> {
+    GuardReport::new(shifts).strategy_2().map(|choice| choice.product)
+}
+
+const TIMESTAMP : &str = "a timestamp like '1518-11-01 00:05'";
+const LINE : &str = "a line like '[1518-11-01 00:05] falls asleep'";
+
+// `YYYY-MM-DD HH:MM`, with optional seconds and either a space or an ISO 8601
+// 'T' between the date and the time. Errors point at the column of `s` where
+// it stopped matching, or at the start for dates that don't exist.
+pub fn parse_datetime(s : &str) -> Result<NaiveDateTime, ParseError> {
+    let mut scan = Scanner::new(s);
+    let (y, m, d, h, min, sec) = timestamp(&mut scan)
+        .ok_or_else(|| ParseError::new(s, TIMESTAMP).at_column(scan.column()))?;
+
+    NaiveDate::from_ymd_opt(y as i32, m, d)
+        .and_then(|date| date.and_hms_opt(h, min, sec))
+        .ok_or_else(|| ParseError::new(s, TIMESTAMP).at_column(1))
+}
+
+fn timestamp(scan : &mut Scanner) -> Option<(u32, u32, u32, u32, u32, u32)> {
+    let y = scan.digits(4)?;
+    scan.eat("-").then_some(())?;
+    let m = scan.digits(2)?;
+    scan.eat("-").then_some(())?;
+    let d = scan.digits(2)?;
+    (scan.eat(" ") || scan.eat("T")).then_some(())?;
+    let h = scan.digits(2)?;
+    scan.eat(":").then_some(())?;
+    let min = scan.digits(2)?;
+    let sec = if scan.eat(":") { scan.digits(2)? } else { 0 };
+    scan.is_done().then_some((y, m, d, h, min, sec))
+}
+
+// `[timestamp] event`. Errors from the timestamp and the event are moved along
+// so that their columns count from the start of the line.
+pub fn parse_line(line : &str) -> Result<(NaiveDateTime, Event), ParseError> {
+    let mut scan = Scanner::new(line);
+    let error = |scan : &Scanner| ParseError::new(line, LINE).at_column(scan.column());
+
+    if !scan.eat("[") {
+        return Err(error(&scan));
+    }
+    let start = scan.column() - 1;
+    let stamp = scan.until(']').ok_or_else(|| error(&scan))?;
+    let dt = parse_datetime(stamp).map_err(|e| shift_column(e, start))?;
+
+    scan.eat("]");
+    if scan.spaces() == 0 {
+        return Err(error(&scan));
+    }
+    let start = scan.column() - 1;
+    let ev = Event::parse(scan.rest()).map_err(|e| shift_column(e, start))?;
+
+    Ok((dt, ev))
+}
+
+fn shift_column(error : ParseError, by : usize) -> ParseError {
+    ParseError { column: error.column.map(|column| column + by), ..error }
+}
+
+pub fn parse_lines(lines : Vec<String>) -> Result<Vec<(NaiveDateTime, Event)>, ParseErrors> {
+    let numbered = lines.iter().enumerate().map(|(i, line)| (i + 1, line.as_str()));
+    Ok(sort_events(aoc_common::parse_numbered(numbered, parse_line)?))
+}
+
+fn sort_events(mut entries : Vec<(NaiveDateTime, Event)>) -> Vec<(NaiveDateTime, Event)> {
+    entries.sort_by_key(|(dt, _)| *dt);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::lines_from_file;
+    use std::io::{self, BufRead};
+    // use timestamps::*;
+
+    #[test]
+    fn parsing_a_line_returns_a_timestamp_event_tuple() {
+        let line = "[2018-12-04 17:37] Guard #10 begins shift";
+        let (dt, ev)= parse_line(line).unwrap();
+
+        assert_eq!(dt, NaiveDate::from_ymd_opt(2018, 12, 4).unwrap().and_hms_opt(17, 37, 0).unwrap());
+        assert_eq!(ev, Event::StartShift(10));
+    }
+
+    #[test]
+    fn can_read_multiple_lines() {
+        let lines = ["[2018-12-04 17:37] Guard #10 begins shift", "[2018-12-03 00:00] Guard #1 begins shift", "[2018-12-05 00:04] falls asleep"]
+            .iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let data = parse_lines(lines).unwrap();
+
+        assert_eq!(3, data.len());
+    }
+
+    #[test]
+    fn can_read_from_input() {
+        let input = io::Cursor::new(
+"[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up".as_bytes());
+        let lines = input.lines().map(|l| l.unwrap()).collect::<Vec<_>>();
+        assert_eq!(17, lines.len());
+
+        let data = parse_lines(lines).unwrap();
+        assert_eq!(17, data.len());
+    }
+
+    #[test]
+    fn can_sort_data() {
+        let input = io::Cursor::new(
+"[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:29] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-05 00:55] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep".as_bytes());
+
+        let lines = input.lines().map(|l| l.unwrap()).collect::<Vec<_>>();
+        let data = parse_lines(lines).unwrap();
+
+        let (ts_first, _) = &data[0];
+        let (ts_last, _) = &data[data.len()-1];
+
+        println!("{:?}", data);
+        assert_eq!(*ts_first, NaiveDate::from_ymd_opt(1518, 11, 1).unwrap().and_hms_opt(0, 0, 0).unwrap());
+        assert_eq!(*ts_last, NaiveDate::from_ymd_opt(1518, 11, 5).unwrap().and_hms_opt(0, 55, 0).unwrap());
+    }
+
+    #[test]
+    fn can_read_input_file() {
+        let lines = lines_from_file("input.txt");
+        let data = parse_lines(lines).unwrap();
+
+        assert_eq!(1098, data.len());
+
+    }
+
+    #[test]
+    fn input_always_has_start_shift_first() {
+        let lines = lines_from_file("input.txt");
+        let data = parse_lines(lines).unwrap();
+        let (_, ref ev) = data[0];
+
+        assert!(matches!(ev, Event::StartShift(_)));
+    }
+
+    #[test]
+    fn shifts_are_calculated_properly() {
+        let lines = lines_from_file("input.txt");
+        let data = parse_lines(lines).unwrap();
+        let shifts = Shift::from_events(&data);
+
+        // assert!(false);
+        assert_eq!(shifts[0].guard(), 2141);
+        assert_eq!(shifts[0].chart(), "........................##################################..".to_string());
+
+        assert_eq!(shifts[shifts.len()-1].guard(), 769);
+        assert_eq!(shifts[shifts.len()-1].chart(), "................#################################...........".to_string());
+    }
+
+    #[test]
+    fn seconds_and_iso_separators_are_accepted() {
+        let expected = NaiveDate::from_ymd_opt(1518, 11, 1).unwrap().and_hms_opt(0, 5, 30).unwrap();
+        assert_eq!(parse_line("[1518-11-01 00:05:30] falls asleep"), Ok((expected, Event::FallAsleep)));
+        assert_eq!(parse_line("[1518-11-01T00:05:30] falls asleep"), Ok((expected, Event::FallAsleep)));
+        assert_eq!(parse_datetime("1518-11-01T00:05"), Ok(expected.with_second(0).unwrap()));
+    }
+
+    #[test]
+    fn malformed_lines_are_errors() {
+        assert_eq!(parse_line("2018-12-04 17:37 falls asleep").err(),
+            Some(ParseError::new("2018-12-04 17:37 falls asleep", LINE).at_column(1)));
+        assert_eq!(parse_line("[2018-12-04 17] falls asleep").err(), Some(ParseError::new("2018-12-04 17", TIMESTAMP).at_column(15)));
+        assert_eq!(parse_line("[2018-02-30 00:01] falls asleep").err(), Some(ParseError::new("2018-02-30 00:01", TIMESTAMP).at_column(2)));
+        assert_eq!(parse_line("[2018-12-04 00:01] Guard #x begins shift").err(),
+            Some(ParseError::new("Guard #x begins shift", "a guard like 'Guard #10 begins shift'").at_column(27)));
+        assert!(parse_line("[2018-12-04 00:01] dances").is_err());
+        assert!(parse_line("[2018-12-04 00:01] ").is_err());
+        assert!(parse_line("[2018-12-04 00:01] wibble").is_err());
+        assert!(parse_line("[2018-12-04 00:01] foo").is_err());
+        assert!(parse_line("[2018-12-04 00:01]falls asleep").is_err());
+        assert!(parse_line("[2018-12-04 00:01:5] falls asleep").is_err());
+        assert!(parse_line("[2018-12-04 00:01 falls asleep").is_err());
+    }
+
+    #[test]
+    fn errors_point_at_where_the_line_went_wrong() {
+        let error = parse_line("[1518-11-01 00:05] wakes up late").unwrap_err().at_line(3);
+        assert_eq!(error.to_string(), "line 3, column 28: expected 'Guard #N begins shift', 'falls asleep' or 'wakes up', found 'wakes up late'");
+
+        assert_eq!(parse_line("[1518-11-01 0:05] wakes up").unwrap_err().column, Some(13));
+        assert_eq!(parse_line("[1518-11-01 00:05] falls   ").unwrap_err().column, Some(20));
+    }
+
+    #[test]
+    fn every_malformed_line_is_reported() {
+        let lines = ["[2018-12-04 17:37] Guard #10 begins shift", "[2018-12-05] falls asleep", "[2018-12-05 00:04] falls asleep", "oops"]
+            .iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let errors = parse_lines(lines).unwrap_err();
+        let lines : Vec<_> = errors.0.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec!(Some(2), Some(4)));
+    }
+
+    #[test]
+    fn example_strategies_are_solved() {
+        let shifts = Day4::parse(
+"[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up").unwrap();
+
+        assert_eq!(Day4::part1(&shifts), Some(240));
+        assert_eq!(Day4::part2(&shifts), Some(4455));
+    }
+
+    #[test]
+    fn a_log_where_nobody_sleeps_has_no_answer() {
+        let shifts = Day4::parse("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-02 00:00] Guard #99 begins shift").unwrap();
+        assert_eq!(Day4::part1(&shifts), None);
+        assert_eq!(Day4::part2(&shifts), None);
+    }
+}
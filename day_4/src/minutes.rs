@@ -0,0 +1,158 @@
+use std::fmt;
+
+// A set of minutes into an observation window, one bit per minute. The default
+// window is the midnight hour, but a set can hold minutes of any window.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Minutes {
+    // Trimmed of trailing zero words, so equal sets are equal.
+    words : Vec<u64>,
+}
+
+impl Minutes {
+    pub fn new() -> Minutes {
+        Minutes { words: Vec::new() }
+    }
+
+    // Every minute from `start` up to but not including `stop`.
+    pub fn range(start : u32, stop : u32) -> Minutes {
+        (start..stop).collect()
+    }
+
+    pub fn insert(&mut self, minute : u32) {
+        let (word, bit) = position(minute);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= bit;
+    }
+
+    pub fn remove(&mut self, minute : u32) {
+        let (word, bit) = position(minute);
+        if let Some(w) = self.words.get_mut(word) {
+            *w &= !bit;
+        }
+        self.trim();
+    }
+
+    pub fn contains(&self, minute : u32) -> bool {
+        let (word, bit) = position(minute);
+        self.words.get(word).is_some_and(|w| w & bit != 0)
+    }
+
+    pub fn count(&self) -> u32 {
+        self.words.iter().map(|w| w.count_ones()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn union(&self, other : &Minutes) -> Minutes {
+        let (long, short) = if self.words.len() >= other.words.len() { (self, other) } else { (other, self) };
+        let mut words = long.words.clone();
+        for (w, o) in words.iter_mut().zip(short.words.iter()) {
+            *w |= o;
+        }
+        Minutes { words }
+    }
+
+    pub fn intersection(&self, other : &Minutes) -> Minutes {
+        let words = self.words.iter().zip(other.words.iter()).map(|(w, o)| w & o).collect();
+        let mut minutes = Minutes { words };
+        minutes.trim();
+        minutes
+    }
+
+    pub fn difference(&self, other : &Minutes) -> Minutes {
+        let words = self.words.iter().enumerate().map(|(i, w)| w & !other.words.get(i).unwrap_or(&0)).collect();
+        let mut minutes = Minutes { words };
+        minutes.trim();
+        minutes
+    }
+
+    // The minutes in the set, earliest first.
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, word)| {
+            let mut bits = *word;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let minute = i as u32 * 64 + bits.trailing_zeros();
+                bits &= bits - 1;
+                Some(minute)
+            })
+        })
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+}
+
+fn position(minute : u32) -> (usize, u64) {
+    ((minute / 64) as usize, 1 << (minute % 64))
+}
+
+impl FromIterator<u32> for Minutes {
+    fn from_iter<I : IntoIterator<Item = u32>>(minutes : I) -> Minutes {
+        let mut set = Minutes::new();
+        for minute in minutes {
+            set.insert(minute);
+        }
+        set
+    }
+}
+
+impl fmt::Debug for Minutes {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minutes_can_be_added_and_removed() {
+        let mut minutes = Minutes::new();
+        assert!(minutes.is_empty());
+        minutes.insert(0);
+        minutes.insert(59);
+        minutes.insert(59);
+        assert_eq!(minutes.count(), 2);
+        assert!(minutes.contains(59));
+        assert!(!minutes.contains(60));
+        minutes.remove(0);
+        assert_eq!(minutes.iter().collect::<Vec<_>>(), vec!(59));
+    }
+
+    #[test]
+    fn sets_combine() {
+        let a = Minutes::range(10, 20);
+        let b = Minutes::range(15, 25);
+        assert_eq!(a.union(&b), Minutes::range(10, 25));
+        assert_eq!(a.intersection(&b), Minutes::range(15, 20));
+        assert_eq!(a.difference(&b), Minutes::range(10, 15));
+        assert_eq!(a.intersection(&Minutes::range(40, 50)).count(), 0);
+        assert_eq!(format!("{:?}", Minutes::range(3, 6)), "{3, 4, 5}");
+    }
+
+    #[test]
+    fn windows_can_be_longer_than_an_hour() {
+        let long = Minutes::range(50, 200);
+        assert_eq!(long.count(), 150);
+        assert!(long.contains(199));
+        assert!(!long.contains(200));
+        assert_eq!(long.intersection(&Minutes::range(0, 60)), Minutes::range(50, 60));
+        assert_eq!(long.difference(&Minutes::range(60, 300)), Minutes::range(50, 60));
+        assert_eq!(Minutes::range(0, 10).union(&Minutes::range(130, 131)).iter().last(), Some(130));
+
+        let mut minutes = Minutes::range(130, 131);
+        minutes.remove(130);
+        assert_eq!(minutes, Minutes::new());
+    }
+}
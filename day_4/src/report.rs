@@ -0,0 +1,218 @@
+use crate::event::Event;
+use crate::minutes::Minutes;
+use crate::shift::Shift;
+use crate::window::Window;
+use chrono::NaiveDateTime;
+use std::collections::BTreeMap;
+
+// A minute into the window and how many times a guard was asleep during it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinuteCount {
+    pub minute : u32,
+    pub times : u32,
+}
+
+// Everything one guard slept through, across all of their shifts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuardSleep {
+    pub guard : u32,
+    pub shifts : u32,
+    pub total_minutes : u32,
+    // How many shifts the guard was asleep for each minute of the window.
+    pub histogram : Vec<u32>,
+    // Every minute the guard was asleep on at least one shift.
+    pub ever_asleep : Minutes,
+}
+
+impl GuardSleep {
+    // The minute the guard was most often asleep, preferring the earliest on a
+    // tie, or None if they never slept.
+    pub fn most_slept_minute(&self) -> Option<MinuteCount> {
+        self.histogram
+            .iter()
+            .enumerate()
+            .fold(None, |best : Option<MinuteCount>, (minute, times)| {
+                if *times > best.map_or(0, |b| b.times) {
+                    Some(MinuteCount { minute: minute as u32, times: *times })
+                } else {
+                    best
+                }
+            })
+    }
+}
+
+// The guard and minute a strategy picks, and the puzzle answer they give.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Choice {
+    pub guard : u32,
+    pub minute : u32,
+    pub times : u32,
+    // Widened, as guard ids use the whole of u32.
+    pub product : u64,
+}
+
+impl Choice {
+    fn new(guard : u32, minute : MinuteCount) -> Choice {
+        Choice { guard, minute: minute.minute, times: minute.times, product: u64::from(guard) * u64::from(minute.minute) }
+    }
+}
+
+// Sleep totals and histograms for every guard, ordered by guard id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuardReport {
+    guards : BTreeMap<u32, GuardSleep>,
+}
+
+impl GuardReport {
+    pub fn new(shifts : &[Shift]) -> GuardReport {
+        let mut guards = BTreeMap::new();
+        for shift in shifts {
+            let asleep = shift.asleep();
+            let sleep = guards.entry(shift.guard()).or_insert_with(|| GuardSleep {
+                guard: shift.guard(),
+                shifts: 0,
+                total_minutes: 0,
+                histogram: vec!(0; shift.window().minutes() as usize),
+                ever_asleep: Minutes::new(),
+            });
+            sleep.shifts += 1;
+            sleep.total_minutes += asleep.count();
+            sleep.ever_asleep = sleep.ever_asleep.union(asleep);
+            for minute in asleep.iter() {
+                sleep.histogram[minute as usize] += 1;
+            }
+        }
+        GuardReport { guards }
+    }
+
+    pub fn from_events(events : &[(NaiveDateTime, Event)]) -> GuardReport {
+        GuardReport::new(&Shift::from_events(events))
+    }
+
+    pub fn from_events_in(events : &[(NaiveDateTime, Event)], window : Window) -> GuardReport {
+        GuardReport::new(&Shift::from_events_in(events, window))
+    }
+
+    pub fn guards(&self) -> impl Iterator<Item = &GuardSleep> {
+        self.guards.values()
+    }
+
+    pub fn guard(&self, id : u32) -> Option<&GuardSleep> {
+        self.guards.get(&id)
+    }
+
+    // The minutes on which both guards were asleep, on some shift or other.
+    pub fn asleep_together(&self, a : u32, b : u32) -> Minutes {
+        match (self.guard(a), self.guard(b)) {
+            (Some(a), Some(b)) => a.ever_asleep.intersection(&b.ever_asleep),
+            _ => Minutes::new(),
+        }
+    }
+
+    // Strategy 1: the guard who sleeps the most overall, and their most slept minute.
+    pub fn strategy_1(&self) -> Option<Choice> {
+        let sleepiest = self.guards.values().max_by_key(|sleep| sleep.total_minutes)?;
+        sleepiest.most_slept_minute().map(|minute| Choice::new(sleepiest.guard, minute))
+    }
+
+    // Strategy 2: the guard most frequently asleep on the same minute.
+    pub fn strategy_2(&self) -> Option<Choice> {
+        self.guards
+            .values()
+            .filter_map(|sleep| sleep.most_slept_minute().map(|minute| Choice::new(sleep.guard, minute)))
+            .max_by_key(|choice| choice.times)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE : &str = "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up";
+
+    fn report() -> GuardReport {
+        GuardReport::from_events(&crate::parse_lines(aoc_common::lines(EXAMPLE)).unwrap())
+    }
+
+    #[test]
+    fn sleep_is_totalled_per_guard() {
+        let report = report();
+        let totals : Vec<_> = report.guards().map(|sleep| (sleep.guard, sleep.shifts, sleep.total_minutes)).collect();
+        assert_eq!(totals, vec!((10, 2, 50), (99, 3, 30)));
+
+        let ten = report.guard(10).unwrap();
+        assert_eq!(ten.histogram.len(), 60);
+        assert_eq!(ten.histogram[5], 1);
+        assert_eq!(ten.histogram[24], 2);
+        assert_eq!(ten.most_slept_minute(), Some(MinuteCount { minute: 24, times: 2 }));
+        assert_eq!(report.guard(99).unwrap().most_slept_minute(), Some(MinuteCount { minute: 45, times: 3 }));
+        assert_eq!(report.guard(11), None);
+    }
+
+    #[test]
+    fn both_strategies_name_a_guard_and_minute() {
+        let report = report();
+        assert_eq!(report.strategy_1(), Some(Choice { guard: 10, minute: 24, times: 2, product: 240 }));
+        assert_eq!(report.strategy_2(), Some(Choice { guard: 99, minute: 45, times: 3, product: 4455 }));
+    }
+
+    #[test]
+    fn guards_can_be_compared() {
+        let report = report();
+        assert_eq!(report.guard(10).unwrap().ever_asleep, Minutes::range(5, 29).union(&Minutes::range(30, 55)));
+        assert_eq!(report.guard(99).unwrap().ever_asleep, Minutes::range(36, 55));
+        assert_eq!(report.asleep_together(10, 99), Minutes::range(36, 55));
+        assert_eq!(report.asleep_together(10, 11), Minutes::new());
+    }
+
+    #[test]
+    fn reports_are_sized_to_the_window() {
+        let events = crate::parse_lines(aoc_common::lines("[1518-11-01 21:50] Guard #7 begins shift
+[1518-11-01 22:10] falls asleep
+[1518-11-01 23:20] wakes up
+[1518-11-02 21:55] Guard #7 begins shift
+[1518-11-03 00:50] falls asleep")).unwrap();
+        let window = Window::new(chrono::NaiveTime::from_hms_opt(22, 0, 0).unwrap(), 180).unwrap();
+        let report = GuardReport::from_events_in(&events, window);
+        let seven = report.guard(7).unwrap();
+        assert_eq!(seven.histogram.len(), 180);
+        assert_eq!(seven.total_minutes, 70 + 10);
+        assert_eq!(report.strategy_1(), Some(Choice { guard: 7, minute: 10, times: 1, product: 70 }));
+    }
+
+    #[test]
+    fn guards_who_never_sleep_give_no_answer() {
+        let report = GuardReport::from_events(&crate::parse_lines(vec!("[1518-11-01 00:00] Guard #10 begins shift".to_string())).unwrap());
+        assert_eq!(report.guard(10).unwrap().total_minutes, 0);
+        assert_eq!(report.guard(10).unwrap().most_slept_minute(), None);
+        assert_eq!(report.strategy_1(), None);
+        assert_eq!(report.strategy_2(), None);
+        assert_eq!(GuardReport::new(&[]).strategy_1(), None);
+    }
+
+    #[test]
+    fn the_largest_guard_ids_still_give_an_answer() {
+        let events = crate::parse_lines(aoc_common::lines("[1518-11-01 00:00] Guard #4294967295 begins shift
+[1518-11-01 00:58] falls asleep
+[1518-11-01 00:59] wakes up")).unwrap();
+        let report = GuardReport::from_events(&events);
+        assert_eq!(report.strategy_1().map(|choice| choice.product), Some(4294967295 * 58));
+        assert_eq!(report.strategy_2().map(|choice| choice.product), Some(249_108_103_110));
+    }
+}
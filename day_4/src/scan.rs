@@ -0,0 +1,97 @@
+// A cursor over one line of the log, for the hand-written grammar in `parse_line`
+// and `Event::parse`. It only ever steps over ASCII, so `column` stays in step
+// with the characters a reader would count.
+pub(crate) struct Scanner<'a> {
+    s : &'a str,
+    pos : usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(s : &'a str) -> Scanner<'a> {
+        Scanner { s, pos: 0 }
+    }
+
+    // 1-based column of the next unread character.
+    pub fn column(&self) -> usize {
+        self.s[..self.pos].chars().count() + 1
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pos == self.s.len()
+    }
+
+    // Steps over `expected` if the rest of the line starts with it.
+    pub fn eat(&mut self, expected : &str) -> bool {
+        if self.rest().starts_with(expected) {
+            self.pos += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    // Steps over spaces and tabs, returning how many there were.
+    pub fn spaces(&mut self) -> usize {
+        let count = self.rest().bytes().take_while(|b| *b == b' ' || *b == b'\t').count();
+        self.pos += count;
+        count
+    }
+
+    // Exactly `width` digits. Nothing is consumed unless they are all there.
+    pub fn digits(&mut self, width : usize) -> Option<u32> {
+        let digits = self.rest().get(..width).filter(|d| d.bytes().all(|b| b.is_ascii_digit()))?;
+        let value = digits.parse().ok()?;
+        self.pos += width;
+        Some(value)
+    }
+
+    // One or more digits.
+    pub fn number(&mut self) -> Option<u32> {
+        let width = self.rest().bytes().take_while(|b| b.is_ascii_digit()).count();
+        if width == 0 {
+            return None;
+        }
+        self.digits(width)
+    }
+
+    // Everything up to (but not including) `end`, if it appears.
+    pub fn until(&mut self, end : char) -> Option<&'a str> {
+        let rest = self.rest();
+        let width = rest.find(end)?;
+        self.pos += width;
+        Some(&rest[..width])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_must_fill_the_width() {
+        let mut scan = Scanner::new("12a");
+        assert_eq!(scan.digits(3), None);
+        assert_eq!(scan.column(), 1);
+        assert_eq!(scan.digits(2), Some(12));
+        assert_eq!(scan.column(), 3);
+        assert_eq!(scan.number(), None);
+    }
+
+    #[test]
+    fn the_column_follows_what_was_eaten() {
+        let mut scan = Scanner::new("[x]  #42");
+        assert!(scan.eat("["));
+        assert_eq!(scan.until(']'), Some("x"));
+        assert!(scan.eat("]"));
+        assert_eq!(scan.spaces(), 2);
+        assert!(!scan.eat("42"));
+        assert!(scan.eat("#"));
+        assert_eq!(scan.number(), Some(42));
+        assert!(scan.is_done());
+        assert_eq!(scan.column(), 9);
+    }
+}
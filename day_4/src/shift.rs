@@ -1,5 +1,8 @@
-use activity::Activity;
-use event::Event;
+use crate::activity::Activity;
+use crate::event::Event;
+use crate::minutes::Minutes;
+use crate::validate::{self, Anomaly};
+use crate::window::Window;
 use chrono::prelude::*;
 
 #[derive(Debug)]
@@ -21,45 +24,65 @@ pub struct Shift {
 }
 
 impl Shift {
+    #[cfg(test)]
     fn new(dt : NaiveDateTime, id : u32) -> Shift {
-      Shift { when: dt, guard_id: id, activity: Activity::new(dt), state: GuardState::Awake }
+      Shift::in_window(dt, id, Window::default())
     }
 
-    pub fn from_events(events : &Vec<(NaiveDateTime, Event)>) -> Vec<Shift> {
-      let mut result : Vec<Shift> = Vec::new();
+    fn in_window(dt : NaiveDateTime, id : u32, window : Window) -> Shift {
+      Shift { when: dt, guard_id: id, activity: Activity::with_window(dt, window), state: GuardState::Awake }
+    }
 
-      if events.is_empty() {
-        return result;
-      }
+    pub fn from_events(events : &[(NaiveDateTime, Event)]) -> Vec<Shift> {
+      Shift::from_events_in(events, Window::default())
+    }
 
-      let (dt, ref ev) = events[0];
-      let id : Option<u32> = match ev {
-        Event::StartShift(id) => Some(*id),
-        _ => None
-      };
-      let events_i = events.iter().skip(1);
-      let mut shift : Shift = Shift::new(dt, id.unwrap());
-
-      for (dt, ev) in events_i {
-        match ev {
-          Event::StartShift(id) => {
-                shift.finish();
-                result.push(shift);
-                shift = Shift::new(*dt, *id);
+    // Builds shifts that watch the guards for `window` rather than the midnight hour.
+    // Events before the first shift starts belong to no one and are skipped;
+    // validate::check reports them, along with anything else odd in the log.
+    pub fn from_events_in(events : &[(NaiveDateTime, Event)], window : Window) -> Vec<Shift> {
+      let mut result : Vec<Shift> = Vec::new();
+      let mut shift : Option<Shift> = None;
+
+      for (dt, ev) in events {
+        match (ev, shift.as_mut()) {
+          (Event::StartShift(id), _) => {
+            if let Some(mut finished) = shift.take() {
+              finished.finish();
+              result.push(finished);
+            }
+            shift = Some(Shift::in_window(*dt, *id, window));
+          },
+          (Event::FallAsleep, Some(current)) => {
+            current.fall_asleep(*dt);
           },
-          Event::FallAsleep => {
-            shift.fall_asleep(*dt);
+          (Event::WakeUp, Some(current)) => {
+            current.wake_up(*dt);
           },
-          Event::WakeUp => {
-            shift.wake_up(*dt);
-          }
-        } 
+          (_, None) => ()
+        }
       }
 
-      shift.finish();
-      result.push(shift);
+      if let Some(mut finished) = shift {
+        finished.finish();
+        result.push(finished);
+      }
 
-      (*result).to_vec()
+      result
+    }
+
+    // Like from_events_in, but refuses a log with anything wrong with it.
+    pub fn from_events_strict(events : &[(NaiveDateTime, Event)], window : Window) -> Result<Vec<Shift>, Vec<Anomaly>> {
+      let anomalies = validate::check(events, window);
+      if anomalies.is_empty() {
+        Ok(Shift::from_events_in(events, window))
+      } else {
+        Err(anomalies)
+      }
+    }
+
+    pub fn when(&self) -> NaiveDateTime {
+      self.when
     }
 
     pub fn guard(&self) -> u32 {
@@ -70,19 +93,24 @@ impl Shift {
       self.activity.chart()
     }
 
+    pub fn asleep(&self) -> &Minutes {
+      self.activity.asleep()
+    }
+
+    pub fn window(&self) -> Window {
+      self.activity.window()
+    }
+
     fn finish(&mut self) {
-      let stop = self.activity.shift_date().and_hms(1,0,0);
+      let stop = self.activity.window_end();
       self.wake_up(stop);
     }
 
-    fn is_awake(&self) -> bool {
-      match self.state {
-        GuardState::Awake => true,
-        _ => false
-      }
+    pub fn is_awake(&self) -> bool {
+      matches!(self.state, GuardState::Awake)
     }
 
-    fn is_asleep(&self) -> bool {
+    pub fn is_asleep(&self) -> bool {
       !self.is_awake()
     }
 
@@ -120,7 +148,7 @@ mod tests {
   #[test]
   fn a_single_shift_start_means_one_entry_wide_awake() {
     let events : Vec<(NaiveDateTime, Event)> = vec!(
-      (NaiveDate::from_ymd(2018, 12, 5).and_hms(23, 56, 0), Event::StartShift(10))
+      (NaiveDate::from_ymd_opt(2018, 12, 5).unwrap().and_hms_opt(23, 56, 0).unwrap(), Event::StartShift(10))
     );
     let shifts = Shift::from_events(&events);
 
@@ -132,8 +160,8 @@ mod tests {
   #[test]
   fn two_shifts_means_two_wide_awake_entries() {
     let events : Vec<(NaiveDateTime, Event)> = vec!(
-      (NaiveDate::from_ymd(2018, 12, 5).and_hms(23, 56, 0), Event::StartShift(10)),
-      (NaiveDate::from_ymd(2018, 12, 7).and_hms(0, 3, 0), Event::StartShift(11))
+      (NaiveDate::from_ymd_opt(2018, 12, 5).unwrap().and_hms_opt(23, 56, 0).unwrap(), Event::StartShift(10)),
+      (NaiveDate::from_ymd_opt(2018, 12, 7).unwrap().and_hms_opt(0, 3, 0).unwrap(), Event::StartShift(11))
     );
     let shifts = Shift::from_events(&events);
     assert_eq!(2, shifts.len());
@@ -145,37 +173,50 @@ mod tests {
     assert_eq!(11, shift.guard());
   }
 
+  #[test]
+  fn events_before_the_first_shift_are_skipped() {
+    let events : Vec<(NaiveDateTime, Event)> = vec!(
+      (NaiveDate::from_ymd_opt(2018, 12, 5).unwrap().and_hms_opt(0, 10, 0).unwrap(), Event::FallAsleep),
+      (NaiveDate::from_ymd_opt(2018, 12, 5).unwrap().and_hms_opt(23, 56, 0).unwrap(), Event::StartShift(10))
+    );
+    let shifts = Shift::from_events(&events);
+
+    assert_eq!(1, shifts.len());
+    assert!(!shifts[0].chart().contains('#'));
+    assert!(Shift::from_events_strict(&events, Window::default()).is_err());
+  }
+
   #[test]
   fn a_guard_always_starts_awake_on_a_shift() {
-    let shift = Shift::new(NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0), 1);
+    let shift = Shift::new(NaiveDate::from_ymd_opt(2018, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap(), 1);
 
     assert!(shift.is_awake());
   }
 
   #[test]
   fn when_a_guard_falls_asleep_their_state_is_asleep() {
-    let mut shift = Shift::new(NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0), 1);
-    shift.fall_asleep(NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 55, 0));
+    let mut shift = Shift::new(NaiveDate::from_ymd_opt(2018, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap(), 1);
+    shift.fall_asleep(NaiveDate::from_ymd_opt(2018, 1, 1).unwrap().and_hms_opt(0, 55, 0).unwrap());
 
     assert!(shift.is_asleep());
   }
 
   #[test]
   fn when_a_guard_wakes_up_after_sleeping_the_sleep_is_recorded() {
-    let mut shift = Shift::new(NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0), 1);
-    shift.fall_asleep(NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 55, 0));
+    let mut shift = Shift::new(NaiveDate::from_ymd_opt(2018, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap(), 1);
+    shift.fall_asleep(NaiveDate::from_ymd_opt(2018, 1, 1).unwrap().and_hms_opt(0, 55, 0).unwrap());
 
     assert!(shift.is_asleep());
 
-    shift.wake_up(NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 58, 0));
+    shift.wake_up(NaiveDate::from_ymd_opt(2018, 1, 1).unwrap().and_hms_opt(0, 58, 0).unwrap());
 
     assert_eq!(shift.chart(), ".......................................................###..".to_string());
   }
 
   #[test]
   fn when_a_guard_finished_their_shift_asleep_the_sleep_is_recorded() {
-    let mut shift = Shift::new(NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0), 1);
-    shift.fall_asleep(NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 55, 0));
+    let mut shift = Shift::new(NaiveDate::from_ymd_opt(2018, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap(), 1);
+    shift.fall_asleep(NaiveDate::from_ymd_opt(2018, 1, 1).unwrap().and_hms_opt(0, 55, 0).unwrap());
 
     shift.finish();
     assert_eq!(shift.chart(), ".......................................................#####".to_string());
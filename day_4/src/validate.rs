@@ -0,0 +1,161 @@
+use crate::event::Event;
+use crate::window::Window;
+use chrono::prelude::*;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Problem {
+    // A guard fell asleep while already asleep.
+    DuplicateSleep,
+    // A guard woke up without having fallen asleep.
+    OrphanWakeUp,
+    // Someone fell asleep or woke up before any guard had started a shift.
+    BeforeAnyShift,
+    // A guard fell asleep or woke up outside their shift's window.
+    OutsideWindow,
+    // A shift started for a window that another shift already covers.
+    OverlappingShift,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            Problem::DuplicateSleep => "fell asleep while already asleep",
+            Problem::OrphanWakeUp => "woke up without falling asleep",
+            Problem::BeforeAnyShift => "event before any shift started",
+            Problem::OutsideWindow => "event outside the shift's window",
+            Problem::OverlappingShift => "shift overlaps the one before",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+// Something wrong with a log, and the time of the event it was found at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    pub at : NaiveDateTime,
+    pub problem : Problem,
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.at.format("%Y-%m-%d %H:%M"), self.problem)
+    }
+}
+
+// Walks a sorted log the way Shift::from_events does, reporting everything it
+// would otherwise skip over or quietly put right.
+pub fn check(events : &[(NaiveDateTime, Event)], window : Window) -> Vec<Anomaly> {
+    let mut anomalies = Vec::new();
+    // The open and close of the current shift's window, and whether its guard is asleep.
+    let mut shift : Option<(NaiveDateTime, NaiveDateTime)> = None;
+    let mut asleep = false;
+
+    for (at, event) in events {
+        let mut report = |problem| anomalies.push(Anomaly { at: *at, problem });
+        match (event, shift) {
+            (Event::StartShift(_), previous) => {
+                let (opens, closes) = window.on(window.date_for(at));
+                // Windows longer than a day can overlap without opening together.
+                if previous.is_some_and(|(_, previous_closes)| opens < previous_closes) {
+                    report(Problem::OverlappingShift);
+                }
+                shift = Some((opens, closes));
+                asleep = false;
+            },
+            (_, None) => report(Problem::BeforeAnyShift),
+            (Event::FallAsleep, Some((opens, closes))) => {
+                if *at < opens || *at >= closes {
+                    report(Problem::OutsideWindow);
+                }
+                if asleep {
+                    report(Problem::DuplicateSleep);
+                }
+                asleep = true;
+            },
+            (Event::WakeUp, Some((opens, closes))) => {
+                if *at < opens || *at > closes {
+                    report(Problem::OutsideWindow);
+                }
+                if !asleep {
+                    report(Problem::OrphanWakeUp);
+                }
+                asleep = false;
+            },
+        }
+    }
+    anomalies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events(log : &str) -> Vec<(NaiveDateTime, Event)> {
+        crate::parse_lines(aoc_common::lines(log)).unwrap()
+    }
+
+    fn at(day : u32, h : u32, m : u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(1518, 11, day).unwrap().and_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn a_tidy_log_has_no_anomalies() {
+        let log = events("[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up");
+        assert_eq!(check(&log, Window::default()), vec!());
+    }
+
+    #[test]
+    fn the_puzzle_input_is_tidy() {
+        let log = crate::parse_lines(aoc_common::lines_from_file("input.txt")).unwrap();
+        assert_eq!(check(&log, Window::default()), vec!());
+    }
+
+    #[test]
+    fn every_anomaly_is_reported_with_its_time() {
+        let log = events("[1518-11-01 00:01] falls asleep
+[1518-11-01 00:02] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:07] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] wakes up
+[1518-11-01 00:45] Guard #11 begins shift
+[1518-11-01 01:10] falls asleep");
+        assert_eq!(check(&log, Window::default()), vec!(
+            Anomaly { at: at(1, 0, 1), problem: Problem::BeforeAnyShift },
+            Anomaly { at: at(1, 0, 7), problem: Problem::DuplicateSleep },
+            Anomaly { at: at(1, 0, 30), problem: Problem::OrphanWakeUp },
+            Anomaly { at: at(1, 0, 45), problem: Problem::OverlappingShift },
+            Anomaly { at: at(1, 1, 10), problem: Problem::OutsideWindow }));
+    }
+
+    #[test]
+    fn shifts_overlap_when_their_windows_do() {
+        let log = events("[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-02 00:30] Guard #11 begins shift
+[1518-11-05 00:10] Guard #12 begins shift");
+        let two_days = Window::new(NaiveTime::from_hms_opt(0, 0, 0).unwrap(), 48 * 60).unwrap();
+        assert_eq!(check(&log, two_days), vec!(Anomaly { at: at(2, 0, 30), problem: Problem::OverlappingShift }));
+        assert_eq!(check(&log, Window::default()), vec!());
+    }
+
+    #[test]
+    fn anomalies_describe_themselves() {
+        let anomaly = Anomaly { at: at(3, 0, 7), problem: Problem::DuplicateSleep };
+        assert_eq!(anomaly.to_string(), "1518-11-03 00:07: fell asleep while already asleep");
+    }
+
+    #[test]
+    fn strict_mode_refuses_a_bad_log() {
+        let log = events("[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:25] wakes up");
+        assert_eq!(crate::shift::Shift::from_events_strict(&log, Window::default()).err(),
+            Some(vec!(Anomaly { at: at(1, 0, 25), problem: Problem::OrphanWakeUp })));
+        assert_eq!(crate::shift::Shift::from_events(&log).len(), 1);
+    }
+}
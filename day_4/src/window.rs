@@ -0,0 +1,82 @@
+use chrono::prelude::*;
+use chrono::Duration;
+
+// The stretch of each day that a log watches the guards for: `minutes` long,
+// starting at `start`. It may run past midnight into the next day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Window {
+    start : NaiveTime,
+    minutes : u32,
+}
+
+impl Default for Window {
+    // The puzzle only cares about the midnight hour.
+    fn default() -> Window {
+        Window { start: NaiveTime::MIN, minutes: 60 }
+    }
+}
+
+impl Window {
+    // None for a window with no minutes in it.
+    pub fn new(start : NaiveTime, minutes : u32) -> Option<Window> {
+        (minutes > 0).then_some(Window { start, minutes })
+    }
+
+    pub fn start(&self) -> NaiveTime {
+        self.start
+    }
+
+    pub fn minutes(&self) -> u32 {
+        self.minutes
+    }
+
+    // When the window that starts on `date` opens and closes.
+    pub fn on(&self, date : NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+        let opens = date.and_time(self.start);
+        (opens, opens + Duration::minutes(i64::from(self.minutes)))
+    }
+
+    // The date of the first window that hasn't closed by `date_time`. A guard
+    // who starts their shift then is on duty for that window.
+    pub fn date_for(&self, date_time : &NaiveDateTime) -> NaiveDate {
+        let days_long = i64::from(self.minutes / (24 * 60)) + 1;
+        let mut date = date_time.date() - Duration::days(days_long);
+        while self.on(date).1 <= *date_time {
+            date = date.succ_opt().unwrap();
+        }
+        date
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day : u32, h : u32, m : u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(1518, 11, day).unwrap().and_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn the_midnight_hour_belongs_to_the_next_midnight() {
+        let window = Window::default();
+        assert_eq!(window.date_for(&at(1, 23, 58)), at(2, 0, 0).date());
+        assert_eq!(window.date_for(&at(1, 0, 5)), at(1, 0, 0).date());
+        assert_eq!(window.date_for(&at(1, 0, 59)), at(1, 0, 0).date());
+        assert_eq!(window.date_for(&at(1, 1, 0)), at(2, 0, 0).date());
+    }
+
+    #[test]
+    fn windows_can_cross_midnight() {
+        let window = Window::new(NaiveTime::from_hms_opt(22, 0, 0).unwrap(), 6 * 60).unwrap();
+        assert_eq!(window.on(at(1, 0, 0).date()), (at(1, 22, 0), at(2, 4, 0)));
+        assert_eq!(window.date_for(&at(1, 21, 30)), at(1, 0, 0).date());
+        assert_eq!(window.date_for(&at(2, 3, 0)), at(1, 0, 0).date());
+        assert_eq!(window.date_for(&at(2, 4, 0)), at(2, 0, 0).date());
+    }
+
+    #[test]
+    fn a_window_needs_at_least_one_minute() {
+        assert_eq!(Window::new(NaiveTime::MIN, 0), None);
+        assert_eq!(Window::new(NaiveTime::MIN, 60), Some(Window::default()));
+    }
+}
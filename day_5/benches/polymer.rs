@@ -0,0 +1,42 @@
+use aoc_common::Lcg;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day_5::{p_reduce, remove_and_reduce};
+use std::hint::black_box;
+
+// A polymer of `len` units drawn from the first `types` letters in either
+// polarity.
+fn polymer(len : usize, types : u64) -> String {
+    let mut rng = Lcg::new(len as u64);
+    (0..len)
+        .map(|_| {
+            let unit = (b'a' + rng.below(types) as u8) as char;
+            if rng.below(2) == 0 { unit } else { unit.to_ascii_uppercase() }
+        })
+        .collect()
+}
+
+fn reduce(c : &mut Criterion) {
+    let mut group = c.benchmark_group("day_5/p_reduce");
+    for len in [1_000, 10_000, 50_000] {
+        let polymer = polymer(len, 26);
+        group.bench_with_input(BenchmarkId::from_parameter(len), &polymer, |b, polymer| {
+            b.iter(|| black_box(p_reduce(polymer.clone()).len()))
+        });
+    }
+    group.finish();
+}
+
+fn remove_units(c : &mut Criterion) {
+    let mut group = c.benchmark_group("day_5/remove_and_reduce");
+    group.sample_size(20);
+    for len in [1_000, 10_000, 50_000] {
+        let polymer = polymer(len, 26);
+        group.bench_with_input(BenchmarkId::from_parameter(len), &polymer, |b, polymer| {
+            b.iter(|| black_box(remove_and_reduce(polymer.clone())))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, reduce, remove_units);
+criterion_main!(benches);
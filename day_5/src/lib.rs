@@ -0,0 +1,162 @@
+use aoc_common::{ParseError, ParseErrors, Puzzle};
+
+pub struct Day5;
+
+impl Puzzle for Day5 {
+    const DAY : u32 = 5;
+
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = Option<usize>;
+
+    fn parse(input : &str) -> Result<String, ParseErrors> {
+        let polymer = input.trim();
+        if let Some((i, unit)) = polymer.chars().enumerate().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            // Blank lines and spaces trimmed from the front still count towards the position.
+            let leading = &input[..input.len() - input.trim_start().len()];
+            let line = leading.matches('\n').count() + 1;
+            let indent = leading.rsplit('\n').next().map_or(0, |s| s.chars().count());
+            return Err(ParseError::new(&unit.to_string(), "a polymer made only of letters").at_line(line).at_column(indent + i + 1).into());
+        }
+        Ok(polymer.to_string())
+    }
+
+    fn part1(polymer : &String) -> usize {
+        p_reduce(polymer.clone()).len()
+    }
+
+    fn part2(polymer : &String) -> Option<usize> {
+        remove_and_reduce(polymer.clone()).into_iter().min()
+    }
+}
+
+pub fn will_cancel(a : char, b : char) -> bool {
+    let a_lower = a.to_lowercase().collect::<Vec<_>>()[0];
+    let b_lower = b.to_lowercase().collect::<Vec<_>>()[0];
+    a != b && a_lower == b_lower 
+}
+
+pub fn p_reduce(s : String) -> String {
+    let acc : Vec<Option<char>> = vec!();
+    let result : String = 
+        s.chars()
+            .fold(acc, |mut acc, c| {
+                let len = acc.len();
+                let l = if len == 0 { None } else { acc[len - 1] };
+
+                if l.is_none() || !will_cancel(l.unwrap(), c) {
+                    acc.push(Some(c));
+                } else {
+                    acc.truncate(len - 1);
+                }
+                acc
+            })
+            .iter()
+            .map(|x| x.unwrap())
+            .collect();
+
+    result
+}
+
+pub fn p_reduce_without_unit(s : String, unit : char) -> String {
+    let s = s.replace([unit, unit.to_uppercase().collect::<Vec<_>>()[0]], "");
+    p_reduce(s)
+}
+
+pub fn units(s : String) -> Vec<char> {
+    let mut units : String = s.clone();
+    units.make_ascii_lowercase();
+    let mut unit_chars : Vec<_> = units.chars().collect();
+    unit_chars.sort();
+    unit_chars.dedup();
+
+    unit_chars
+}
+
+pub fn remove_and_reduce(s : String) -> Vec<usize> {
+    let units = units(s.clone());
+
+    let output = units.iter().map(|u| p_reduce_without_unit(s.clone(), *u).len());
+    output.collect::<Vec<_>>()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        assert_eq!(2 + 2, 4);
+    }
+
+    #[test]
+    fn a_and_A_cancel() {
+        assert!(will_cancel('a', 'A'));
+    }
+
+    #[test]
+    fn a_and_a_dont_cancel() {
+        assert!(!will_cancel('a', 'a'));
+    }
+
+    #[test]
+    fn two_different_units_wont_cancel() {
+        assert!(!will_cancel('a', 'B'));
+    }
+
+    #[test]
+    fn a_unit_and_its_opposite_reduce_to_nothing() {
+        assert_eq!(p_reduce("aA".to_string()), "".to_string());
+    }
+
+    #[test]
+    fn aBbA_reduces_to_nothing() {
+        assert_eq!(p_reduce("aBbA".to_string()), "".to_string());
+    }
+
+    #[test]
+    fn aabAAB_is_unchanged() {
+        let s = "aabAAB".to_string();
+        assert_eq!(p_reduce(s.clone()), s);
+    }
+
+    #[test]
+    fn reduces_dabAcCaCBAcCcaDA_to_dabCBAcaDA() {
+        assert_eq!(p_reduce("dabAcCaCBAcCcaDA".to_string()), "dabCBAcaDA".to_string());
+    }
+
+    #[test]
+    fn reduce_without_a_returns_dbCBcD_for_dabAcCaCBAcCcaDA() {
+        assert_eq!(p_reduce_without_unit("dabAcCaCBAcCcaDA".to_string(), 'a'), "dbCBcD".to_string());
+    }
+
+    #[test]
+    fn remove_and_reduce_works() {
+        let result = remove_and_reduce("dabAcCaCBAcCcaDA".to_string());
+        assert_eq!( result, vec!( 6, 8, 4, 6));
+    }
+
+    #[test]
+    fn example_polymer_is_solved() {
+        let polymer = Day5::parse("dabAcCaCBAcCcaDA\n").unwrap();
+        assert_eq!(Day5::part1(&polymer), 10);
+        assert_eq!(Day5::part2(&polymer), Some(4));
+    }
+
+    #[test]
+    fn an_empty_polymer_has_no_best_unit_to_remove() {
+        let polymer = Day5::parse("\n").unwrap();
+        assert_eq!(Day5::part1(&polymer), 0);
+        assert_eq!(Day5::part2(&polymer), None);
+    }
+
+    #[test]
+    fn polymers_are_only_letters() {
+        let errors = Day5::parse("dabA1cC").unwrap_err();
+        assert_eq!(errors.0, vec!(ParseError::new("1", "a polymer made only of letters").at_line(1).at_column(5)));
+
+        let errors = Day5::parse("\n\n  dab-A\n").unwrap_err();
+        assert_eq!(errors.to_string(), "line 3, column 6: expected a polymer made only of letters, found '-'");
+    }
+}